@@ -1,7 +1,4 @@
-use vec_split::{
-    accessors::{IterateAccessor, IterateAccessorMut},
-    SizedVectorArray,
-};
+use vec_split::SizedVectorArray;
 
 fn main() {
     let mut array = [[0.0, 0.0]; 10];
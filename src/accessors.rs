@@ -1,15 +1,23 @@
-use std::{
+use core::{
     marker::PhantomData,
+    mem,
     ops::{Index, IndexMut},
 };
 
-use crate::iter::*;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{error::SplitError, iter::*};
 
 #[macro_export]
 /// Macro to easily implement the Accessor types for your own array types.
 macro_rules! impl_accessors {
     ($type:ty, $getter:ty, unknown $index_constraints:tt) => {
         impl<'a, T, I: $index_constraints> Accessor<T, I> for $type {
+            fn len(&self) -> usize {
+                <$getter>::len(self)
+            }
+
             fn get<'b>(&'b self, index: I) -> Option<&'b T> {
                 <$getter>::get(self, index)
             }
@@ -22,6 +30,10 @@ macro_rules! impl_accessors {
     };
     ($type:ty, $getter:ty, known $index:ty) => {
         impl<'a, T> Accessor<T, $index> for $type {
+            fn len(&self) -> usize {
+                <$getter>::len(self)
+            }
+
             fn get<'b>(&'b self, index: $index) -> Option<&'b T> {
                 <$getter>::get(self, index)
             }
@@ -36,41 +48,298 @@ macro_rules! impl_accessors {
 
 /// Trait used to unify the different accessors (Fast, Safe)
 pub trait Accessor<T: ?Sized, I>: Index<I, Output = T> {
+    /// The amount of items in the accessor.
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Gets an item out of the array. This maybe be a split array, in which
     /// case the operation will take an item out of the vector.
     fn get<'b>(&'b self, index: I) -> Option<&'b T>;
+
+    /// Like [`Self::get`], but returns an error saying why there is no item.
+    fn try_get<'b>(&'b self, index: I) -> Result<&'b T, SplitError>
+    where
+        I: Copy + Into<usize>,
+    {
+        let len = self.len();
+        self.get(index).ok_or(SplitError::IndexOutOfBounds {
+            index: index.into(),
+            len,
+        })
+    }
 }
 /// Trait used to unify the different accessors's mutable variants (Fast, Safe)
 pub trait AccessorMut<T: ?Sized, I>: Accessor<T, I> + IndexMut<I, Output = T> {
     /// Gets an item out of the array mutably. This maybe be a split array, in
     /// which case the operation will take an item out of the vector.
     fn get_mut<'b>(&'b mut self, index: I) -> Option<&'b mut T>;
+
+    /// Like [`Self::get_mut`], but returns an error saying why there is no
+    /// item.
+    fn try_get_mut<'b>(&'b mut self, index: I) -> Result<&'b mut T, SplitError>
+    where
+        I: Copy + Into<usize>,
+    {
+        let len = self.len();
+        self.get_mut(index).ok_or(SplitError::IndexOutOfBounds {
+            index: index.into(),
+            len,
+        })
+    }
 }
 
 /// Trait used to add the `iter` method to structs implementing [`Accessor`]
 pub trait IterateAccessor<'a, T: ?Sized>: Accessor<T, usize> + 'a + Sized {
+    /// Iterates over all items.
+    ///
+    /// # Panics
+    ///
+    /// The iterator panics when it reaches an index without an item, like in
+    /// a jagged array, the same way indexing does.
     fn iter<'b, 'c>(&'b self) -> AccessorIter<'b, 'c, T, Self> {
         AccessorIter {
             phantom_t: PhantomData,
             safe_accessor: self,
             idx: 0,
+            end: self.len(),
         }
     }
 }
 /// Trait used to add the `iter_mut` method to structs implementing
 /// [`AccessorMut`]
 pub trait IterateAccessorMut<'a, T: ?Sized>: AccessorMut<T, usize> + 'a + Sized {
+    /// Iterates over all items mutably.
+    ///
+    /// # Panics
+    ///
+    /// Like [`IterateAccessor::iter`].
     fn iter_mut<'b, 'c>(&'b mut self) -> AccessorIterMut<'b, 'c, T, Self> {
         AccessorIterMut {
             phantom_t: PhantomData,
+            end: self.len(),
             safe_accessor: self,
             idx: 0,
         }
     }
 }
 
+/// Trait used to add bulk copies out of structs implementing [`Accessor`]
+pub trait CopyAccessor<T>: Accessor<T, usize> {
+    /// Copies all items into `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` does not have the same length as the accessor.
+    fn copy_to_slice(&self, dst: &mut [T])
+    where
+        T: Copy,
+    {
+        assert_eq!(self.len(), dst.len(), "lengths of the arrays do not match.");
+        for (i, item) in dst.iter_mut().enumerate() {
+            *item = self[i];
+        }
+    }
+
+    /// Clones all items into a new Vec.
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        (0..self.len()).map(|i| self[i].clone()).collect()
+    }
+}
+/// Trait used to add bulk copies into structs implementing [`AccessorMut`]
+pub trait CopyAccessorMut<T>: AccessorMut<T, usize> {
+    /// Copies all items from `src`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` does not have the same length as the accessor.
+    fn copy_from_slice(&mut self, src: &[T])
+    where
+        T: Copy,
+    {
+        assert_eq!(self.len(), src.len(), "lengths of the arrays do not match.");
+        for (i, item) in src.iter().enumerate() {
+            self[i] = *item;
+        }
+    }
+
+    /// Clones all items from another accessor, which may be of a different
+    /// kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` does not have the same length as the accessor.
+    fn clone_from_accessor<A: Accessor<T, usize> + ?Sized>(&mut self, src: &A)
+    where
+        T: Clone,
+    {
+        assert_eq!(self.len(), src.len(), "lengths of the arrays do not match.");
+        for i in 0..src.len() {
+            self[i].clone_from(&src[i]);
+        }
+    }
+}
+
+/// Trait used to add in-place operations to structs implementing
+/// [`AccessorMut`], like the ones of `[T]`.
+pub trait ModifyAccessorMut<T>: AccessorMut<T, usize> {
+    /// Sets every item to a clone of `value`.
+    fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for i in 0..self.len() {
+            self[i].clone_from(&value);
+        }
+    }
+
+    /// Sets every item to the result of calling `f`.
+    fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
+        for i in 0..self.len() {
+            self[i] = f();
+        }
+    }
+
+    /// Replaces every item with the result of calling `f` on it.
+    fn map_in_place<F: FnMut(&T) -> T>(&mut self, mut f: F) {
+        for i in 0..self.len() {
+            self[i] = f(&self[i]);
+        }
+    }
+
+    /// Calls `f` on every item and its index.
+    fn for_each_indexed<F: FnMut(usize, &mut T)>(&mut self, mut f: F) {
+        for i in 0..self.len() {
+            f(i, &mut self[i]);
+        }
+    }
+
+    /// Swaps the items at `a` and `b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is out of bounds, or if there is no item at one
+    /// of them, like in a jagged array.
+    fn swap(&mut self, a: usize, b: usize)
+    where
+        Self: DisjointAccessorMut<T>,
+    {
+        // Check both items before anything is moved, so that a missing one panics normally.
+        for index in [a, b] {
+            if let Err(err) = self.try_get_mut(index) {
+                panic!("{err}");
+            }
+        }
+        if a == b {
+            return;
+        }
+        // Only one item can be borrowed at a time, so the items are moved through a temporary.
+        // Both items exist, so only a broken accessor can panic while one of them is
+        // duplicated. That would drop it twice, so the guard aborts instead.
+        let guard = AbortOnUnwind;
+        // SAFETY: Self is a DisjointAccessorMut, so a and b are two separate items that are
+        // always found at the same place. Each is read exactly once and written exactly once,
+        // and the guard makes sure nothing is dropped if that is interrupted.
+        unsafe {
+            let item_a = core::ptr::read(&self[a]);
+            let item_b = core::ptr::read(&self[b]);
+            core::ptr::write(&mut self[a], item_b);
+            core::ptr::write(&mut self[b], item_a);
+        }
+        mem::forget(guard);
+    }
+
+    /// Reverses the order of the items.
+    fn reverse(&mut self)
+    where
+        Self: DisjointAccessorMut<T>,
+    {
+        let len = self.len();
+        reverse_range(self, 0, len);
+    }
+
+    /// Rotates the items so that the item at `mid` becomes the first one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    fn rotate_left(&mut self, mid: usize)
+    where
+        Self: DisjointAccessorMut<T>,
+    {
+        let len = self.len();
+        assert!(mid <= len, "mid is too large for array.");
+        reverse_range(self, 0, mid);
+        reverse_range(self, mid, len);
+        reverse_range(self, 0, len);
+    }
+
+    /// Rotates the items so that the last `k` items come first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > len`.
+    fn rotate_right(&mut self, k: usize)
+    where
+        Self: DisjointAccessorMut<T>,
+    {
+        let len = self.len();
+        assert!(k <= len, "k is too large for array.");
+        self.rotate_left(len - k);
+    }
+}
+
+/// Marks accessors whose items can be moved around by [`ModifyAccessorMut`],
+/// like with [`ModifyAccessorMut::swap`].
+///
+/// # Safety
+///
+/// For every index below `len`, `get`, `get_mut`, `index` and `index_mut` must
+/// always return the same item, as long as the accessor is not modified in
+/// other ways. Different indices must return different items, which do not
+/// overlap.
+pub unsafe trait DisjointAccessorMut<T>: AccessorMut<T, usize> {}
+
+// SAFETY: the items of a slice are separate.
+unsafe impl<T> DisjointAccessorMut<T> for [T] {}
+// SAFETY: see [T].
+#[cfg(feature = "alloc")]
+unsafe impl<T> DisjointAccessorMut<T> for alloc::vec::Vec<T> {}
+
+/// Reverses the items in `[start, end)`.
+fn reverse_range<T, A: DisjointAccessorMut<T> + ?Sized>(
+    accessor: &mut A,
+    start: usize,
+    end: usize,
+) {
+    let (mut a, mut b) = (start, end);
+    while a + 1 < b {
+        b -= 1;
+        accessor.swap(a, b);
+        a += 1;
+    }
+}
+
+/// Aborts the program if it is dropped during a panic, by panicking again.
+struct AbortOnUnwind;
+
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) {
+        panic!("an accessor panicked while two items were being swapped.");
+    }
+}
+
 impl<'a, T, A: Accessor<T, usize> + 'a> IterateAccessor<'a, T> for A {}
 impl<'a, T, A: AccessorMut<T, usize> + 'a> IterateAccessorMut<'a, T> for A {}
+impl<T, A: Accessor<T, usize> + ?Sized> CopyAccessor<T> for A {}
+impl<T, A: AccessorMut<T, usize> + ?Sized> CopyAccessorMut<T> for A {}
+impl<T, A: AccessorMut<T, usize> + ?Sized> ModifyAccessorMut<T> for A {}
 
-impl_accessors!(Vec<T>, [T], known usize);
+#[cfg(feature = "alloc")]
+impl_accessors!(alloc::vec::Vec<T>, [T], known usize);
 impl_accessors!([T], [T], known usize);
@@ -0,0 +1,143 @@
+//! Iterators that divide an accessor into disjoint sub-accessors of a fixed
+//! size. Every chunk is an accessor of the same kind, so code written against
+//! [`Accessor`] works on it unchanged.
+
+use core::{iter::FusedIterator, marker::PhantomData};
+
+use crate::*;
+
+/// Trait for accessors that can be divided into two accessors of the same
+/// kind, which do not overlap.
+pub trait SplitAccessor<T: ?Sized>: Accessor<T, usize> + Sized {
+    /// Divides the accessor into two at `mid`, consuming it. The first will
+    /// contain all items from `[0, mid)`, the second all items from
+    /// `[mid, len)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    fn into_split_at(self, mid: usize) -> (Self, Self);
+}
+
+/// An iterator over chunks of `size` items of an accessor. The last chunk is
+/// shorter if the length is not divisible by `size`.
+pub struct Chunks<T: ?Sized, A: SplitAccessor<T>> {
+    phantom_t: PhantomData<fn() -> T>,
+    rest: Option<A>,
+    size: usize,
+}
+
+impl<T: ?Sized, A: SplitAccessor<T>> Chunks<T, A> {
+    pub(crate) fn new(accessor: A, size: usize) -> Self {
+        assert!(size != 0, "chunk size must not be zero.");
+        Self {
+            phantom_t: PhantomData,
+            rest: Some(accessor),
+            size,
+        }
+    }
+
+    fn rest_len(&self) -> usize {
+        self.rest.as_ref().map_or(0, |rest| rest.len())
+    }
+}
+
+impl<T: ?Sized, A: SplitAccessor<T>> Iterator for Chunks<T, A> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.rest_len();
+        if len == 0 {
+            return None;
+        }
+        let (chunk, rest) = self.rest.take()?.into_split_at(self.size.min(len));
+        self.rest = Some(rest);
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rest_len().div_ceil(self.size);
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let skip = n.saturating_mul(self.size).min(self.rest_len());
+        let rest = self.rest.take()?;
+        self.rest = Some(rest.into_split_at(skip).1);
+        self.next()
+    }
+}
+
+impl<T: ?Sized, A: SplitAccessor<T>> DoubleEndedIterator for Chunks<T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let len = self.rest_len();
+        if len == 0 {
+            return None;
+        }
+        let last = match len % self.size {
+            0 => self.size,
+            remainder => remainder,
+        };
+        let (rest, chunk) = self.rest.take()?.into_split_at(len - last);
+        self.rest = Some(rest);
+        Some(chunk)
+    }
+}
+
+impl<T: ?Sized, A: SplitAccessor<T>> ExactSizeIterator for Chunks<T, A> {}
+impl<T: ?Sized, A: SplitAccessor<T>> FusedIterator for Chunks<T, A> {}
+
+/// An iterator over chunks of exactly `size` items of an accessor. The items
+/// left over at the end can be gotten with [`Self::remainder`].
+pub struct ChunksExact<T: ?Sized, A: SplitAccessor<T>> {
+    inner: Chunks<T, A>,
+    remainder: A,
+}
+
+impl<T: ?Sized, A: SplitAccessor<T>> ChunksExact<T, A> {
+    pub(crate) fn new(accessor: A, size: usize) -> Self {
+        assert!(size != 0, "chunk size must not be zero.");
+        let len = accessor.len();
+        let (chunks, remainder) = accessor.into_split_at(len - len % size);
+        Self {
+            inner: Chunks::new(chunks, size),
+            remainder,
+        }
+    }
+
+    /// The items that do not fit into a whole chunk.
+    pub fn remainder(&self) -> &A {
+        &self.remainder
+    }
+
+    /// Like [`Self::remainder`], but consumes the iterator so the remainder
+    /// can be used mutably.
+    pub fn into_remainder(self) -> A {
+        self.remainder
+    }
+}
+
+impl<T: ?Sized, A: SplitAccessor<T>> Iterator for ChunksExact<T, A> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n)
+    }
+}
+
+impl<T: ?Sized, A: SplitAccessor<T>> DoubleEndedIterator for ChunksExact<T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T: ?Sized, A: SplitAccessor<T>> ExactSizeIterator for ChunksExact<T, A> {}
+impl<T: ?Sized, A: SplitAccessor<T>> FusedIterator for ChunksExact<T, A> {}
@@ -0,0 +1,152 @@
+//! Conversions between arrays of vectors and one contiguous column per
+//! dimension.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{error::SplitError, *};
+
+/// How many bytes of vectors are transposed at once by
+/// [`SizedVectorArray::to_columns_fast`]. Small enough to stay in the L1
+/// cache, so each dimension reads the block from cache instead of from RAM.
+const BLOCK_BYTES: usize = 16 * 1024;
+
+#[cfg(feature = "alloc")]
+/// Trait used to add the `into_columns` method to owned arrays of vectors.
+pub trait IntoColumns<T, const D: usize> {
+    /// Moves every dimension of the vectors into its own Vec.
+    fn into_columns(self) -> [Vec<T>; D];
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, V: Into<[T; D]>> IntoColumns<T, D> for Vec<V> {
+    fn into_columns(self) -> [Vec<T>; D] {
+        let mut columns = [(); D].map(|_| Vec::with_capacity(self.len()));
+        for vector in self {
+            for (column, item) in columns.iter_mut().zip(vector.into()) {
+                column.push(item);
+            }
+        }
+        columns
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, V: Into<[T; D]>, const A: usize> IntoColumns<T, D> for [V; A] {
+    fn into_columns(self) -> [Vec<T>; D] {
+        let mut columns = [(); D].map(|_| Vec::with_capacity(A));
+        for vector in self {
+            for (column, item) in columns.iter_mut().zip(vector.into()) {
+                column.push(item);
+            }
+        }
+        columns
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Moves the items of one column per dimension into vectors. All columns
+/// must have the same length.
+pub fn from_columns<T, V: From<[T; D]>, const D: usize>(
+    columns: [Vec<T>; D],
+) -> Result<Vec<V>, SplitError> {
+    let len = columns.first().map_or(0, Vec::len);
+    check_lengths(len, columns.each_ref().map(Vec::len))?;
+    let mut columns = columns.map(Vec::into_iter);
+    Ok((0..len)
+        .map(|_| V::from(columns.each_mut().map(|column| column.next().unwrap())))
+        .collect())
+}
+
+#[cfg(feature = "alloc")]
+/// Clones the items of one column per dimension into vectors. The columns can
+/// be slices, Vecs, or accessors, and must all have the same length.
+pub fn join<T: Clone, V: From<[T; D]>, A: Accessor<T, usize> + ?Sized, const D: usize>(
+    columns: [&A; D],
+) -> Result<Vec<V>, SplitError> {
+    let len = columns.first().map_or(0, |column| column.len());
+    check_lengths(len, columns.map(|column| column.len()))?;
+    Ok((0..len)
+        .map(|i| V::from(columns.map(|column| column[i].clone())))
+        .collect())
+}
+
+#[cfg(feature = "alloc")]
+/// Like [`join`], but writes the columns into default vectors a block at a
+/// time, using [`SizedVectorArray::write_columns`].
+pub fn join_fast<
+    T: Clone,
+    V: RawVector<T, D> + Default,
+    A: Accessor<T, usize> + ?Sized,
+    const D: usize,
+>(
+    columns: [&A; D],
+) -> Result<Vec<V>, SplitError> {
+    let len = columns.first().map_or(0, |column| column.len());
+    let mut vec = (0..len).map(|_| V::default()).collect::<Vec<V>>();
+    vec.write_columns(columns)?;
+    Ok(vec)
+}
+
+/// Checks that every column has the expected length.
+fn check_lengths<const D: usize>(expected: usize, lens: [usize; D]) -> Result<(), SplitError> {
+    for (dim, found) in lens.into_iter().enumerate() {
+        if found != expected {
+            return Err(SplitError::LengthMismatch {
+                dim,
+                expected,
+                found,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The amount of vectors that fit into [`BLOCK_BYTES`].
+fn block_len<V>() -> usize {
+    (BLOCK_BYTES / mem::size_of::<V>().max(1)).max(1)
+}
+
+#[cfg(feature = "alloc")]
+/// Clones the items of the split dimensions into their own Vecs, a block of
+/// vectors at a time.
+pub(crate) fn to_columns_blocked<T: Clone, V, const D: usize>(
+    dims: [StridedSlice<'_, T>; D],
+) -> [Vec<T>; D] {
+    let len = dims.first().map_or(0, |dim| dim.len());
+    let block = block_len::<V>();
+    let mut columns = [(); D].map(|_| Vec::with_capacity(len));
+    let mut start = 0;
+    while start < len {
+        let end = (start + block).min(len);
+        for (column, dim) in columns.iter_mut().zip(dims.iter()) {
+            // SAFETY: start..end is within 0..len, and all dims have the same length.
+            column.extend((start..end).map(|i| unsafe { dim.get_unchecked(i) }.clone()));
+        }
+        start = end;
+    }
+    columns
+}
+
+/// Clones the items of the columns into the split dimensions, a block of
+/// vectors at a time.
+pub(crate) fn write_columns_blocked<T: Clone, V, A: Accessor<T, usize> + ?Sized, const D: usize>(
+    mut dims: [StridedSliceMut<'_, T>; D],
+    columns: [&A; D],
+) -> Result<(), SplitError> {
+    let len = dims.first().map_or(0, |dim| dim.len());
+    check_lengths(len, columns.map(|column| column.len()))?;
+    let block = block_len::<V>();
+    let mut start = 0;
+    while start < len {
+        let end = (start + block).min(len);
+        for (dim, column) in dims.iter_mut().zip(columns) {
+            for i in start..end {
+                // SAFETY: start..end is within 0..len, and all dims have the same length.
+                unsafe { *dim.get_unchecked_mut(i) = column[i].clone() };
+            }
+        }
+        start = end;
+    }
+    Ok(())
+}
@@ -0,0 +1,272 @@
+//! Splitting of arrays whose amount of dimensions is only known at runtime,
+//! like rows loaded from a CSV file. Instead of `[Accessor; D]`, the split
+//! returns a Vec with one accessor per dimension.
+
+use alloc::vec::Vec;
+use core::{
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use crate::{error::SplitError, *};
+
+/// Trait to be implemented for arrays of vectors whose amount of dimensions
+/// is only known at runtime.
+pub trait DynVectorArray<T> {
+    type Accessor<'a>: Accessor<T, usize>
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// Splits the first `dims` dimensions of the vectors into one accessor
+    /// each. Fails if a vector has fewer than `dims` items.
+    fn vec_split_dyn(&self, dims: usize) -> Result<Vec<Self::Accessor<'_>>, SplitError>;
+}
+
+/// Trait to be implemented for arrays of vectors whose amount of dimensions
+/// is only known at runtime, and which can be split mutably.
+pub trait DynVectorArrayMut<T>: DynVectorArray<T> {
+    type AccessorMut<'a>: AccessorMut<T, usize>
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// Like [`DynVectorArray::vec_split_dyn`], but the accessors are mutable.
+    fn vec_split_dyn_mut(&mut self, dims: usize) -> Result<Vec<Self::AccessorMut<'_>>, SplitError>;
+}
+
+/// Checks that every vector has at least `dims` items.
+fn check_components(vectors: &[Vec<impl Sized>], dims: usize) -> Result<(), SplitError> {
+    match vectors.iter().position(|vector| vector.len() < dims) {
+        Some(index) => Err(SplitError::MissingComponent {
+            index,
+            dim: vectors[index].len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+impl<T> DynVectorArray<T> for [Vec<T>] {
+    type Accessor<'a>
+        = DynAccessor<'a, T>
+    where
+        T: 'a;
+
+    fn vec_split_dyn(&self, dims: usize) -> Result<Vec<DynAccessor<'_, T>>, SplitError> {
+        check_components(self, dims)?;
+        Ok((0..dims)
+            .map(|dim| DynAccessor {
+                phantom_t: PhantomData,
+                vectors: self.as_ptr(),
+                len: <[Vec<T>]>::len(self),
+                dim,
+            })
+            .collect())
+    }
+}
+
+impl<T> DynVectorArrayMut<T> for [Vec<T>] {
+    type AccessorMut<'a>
+        = DynAccessorMut<'a, T>
+    where
+        T: 'a;
+
+    fn vec_split_dyn_mut(&mut self, dims: usize) -> Result<Vec<DynAccessorMut<'_, T>>, SplitError> {
+        check_components(self, dims)?;
+        let len = <[Vec<T>]>::len(self);
+        let vectors = self.as_mut_ptr();
+        Ok((0..dims)
+            .map(|dim| DynAccessorMut {
+                phantom_t: PhantomData,
+                vectors,
+                len,
+                dim,
+            })
+            .collect())
+    }
+}
+
+/// A flat buffer of vectors that all have `width` items, stored one after the
+/// other. Splitting it returns [`StridedSlice`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlatArray<B> {
+    buffer: B,
+    width: usize,
+}
+
+impl<B> FlatArray<B> {
+    /// Wraps a buffer of vectors with `width` items each.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is 0.
+    pub fn new(buffer: B, width: usize) -> Self {
+        assert!(width != 0, "width must not be zero.");
+        Self { buffer, width }
+    }
+
+    /// The amount of items in each vector.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+impl<T, B: AsRef<[T]>> DynVectorArray<T> for FlatArray<B> {
+    type Accessor<'a>
+        = StridedSlice<'a, T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn vec_split_dyn(&self, dims: usize) -> Result<Vec<StridedSlice<'_, T>>, SplitError> {
+        let buffer = self.buffer.as_ref();
+        let len = flat_len(buffer.len(), self.width, dims)?;
+        let ptr = buffer.as_ptr();
+        // SAFETY: the buffer holds len vectors of width items, and dims <= width.
+        Ok((0..dims)
+            .map(|dim| unsafe {
+                StridedSlice::from_raw_parts(
+                    ptr.wrapping_add(dim),
+                    len,
+                    self.width * mem::size_of::<T>(),
+                )
+            })
+            .collect())
+    }
+}
+
+impl<T, B: AsRef<[T]> + AsMut<[T]>> DynVectorArrayMut<T> for FlatArray<B> {
+    type AccessorMut<'a>
+        = StridedSliceMut<'a, T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn vec_split_dyn_mut(
+        &mut self,
+        dims: usize,
+    ) -> Result<Vec<StridedSliceMut<'_, T>>, SplitError> {
+        let width = self.width;
+        let buffer = self.buffer.as_mut();
+        let len = flat_len(buffer.len(), width, dims)?;
+        let ptr = buffer.as_mut_ptr();
+        // SAFETY: see DynVectorArray::vec_split_dyn. Each StridedSliceMut only accesses its own dimension.
+        Ok((0..dims)
+            .map(|dim| unsafe {
+                StridedSliceMut::from_raw_parts(
+                    ptr.wrapping_add(dim),
+                    len,
+                    width * mem::size_of::<T>(),
+                )
+            })
+            .collect())
+    }
+}
+
+/// Gets the amount of vectors in a flat buffer, checking that the last one is
+/// complete and that `dims` fits into a vector.
+fn flat_len(buffer_len: usize, width: usize, dims: usize) -> Result<usize, SplitError> {
+    if dims > width {
+        return Err(SplitError::TooManyDimensions { dims, width });
+    }
+    let rest = buffer_len % width;
+    if rest != 0 {
+        return Err(SplitError::MissingComponent {
+            index: buffer_len / width,
+            dim: rest,
+        });
+    }
+    Ok(buffer_len / width)
+}
+
+/// The immutable accessor for one dimension of a `[Vec<T>]` split at runtime.
+pub struct DynAccessor<'a, T> {
+    phantom_t: PhantomData<&'a [Vec<T>]>,
+    vectors: *const Vec<T>,
+    len: usize,
+    dim: usize,
+}
+
+impl<'a, T> Accessor<T, usize> for DynAccessor<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get<'b>(&'b self, index: usize) -> Option<&'b T> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: index is within the slice, which is borrowed for 'a.
+        unsafe { <[T]>::get(&*self.vectors.add(index), self.dim) }
+    }
+}
+
+impl<'a, T> Index<usize> for DynAccessor<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.try_get(index).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+/// The mutable accessor for one dimension of a `[Vec<T>]` split at runtime.
+pub struct DynAccessorMut<'a, T> {
+    phantom_t: PhantomData<&'a mut [Vec<T>]>,
+    vectors: *mut Vec<T>,
+    len: usize,
+    dim: usize,
+}
+
+impl<'a, T> Accessor<T, usize> for DynAccessorMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get<'b>(&'b self, index: usize) -> Option<&'b T> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: index is within the slice, which is borrowed for 'a. Only the Vec itself is
+        // referenced, not the items of other dimensions.
+        unsafe {
+            let vector = &*self.vectors.add(index);
+            (self.dim < vector.len()).then(|| &*vector.as_ptr().add(self.dim))
+        }
+    }
+}
+
+impl<'a, T> AccessorMut<T, usize> for DynAccessorMut<'a, T> {
+    fn get_mut<'b>(&'b mut self, index: usize) -> Option<&'b mut T> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: see get. No other accessor can access this dimension.
+        unsafe {
+            let vector = &mut *self.vectors.add(index);
+            (self.dim < vector.len()).then(|| &mut *vector.as_mut_ptr().add(self.dim))
+        }
+    }
+}
+
+// SAFETY: every index is the item at dim in its own Vec, and the Vecs cannot be resized while
+// they are split.
+unsafe impl<'a, T> DisjointAccessorMut<T> for DynAccessorMut<'a, T> {}
+
+impl<'a, T> Index<usize> for DynAccessorMut<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.try_get(index).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<'a, T> IndexMut<usize> for DynAccessorMut<'a, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.try_get_mut(index)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+}
@@ -0,0 +1,47 @@
+use core::{error::Error, fmt};
+
+/// Errors that can happen when splitting or joining arrays of vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitError {
+    /// A dimension does not have the same amount of items as the others.
+    LengthMismatch {
+        dim: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The vector at `index` has no item for dimension `dim`.
+    MissingComponent { index: usize, dim: usize },
+    /// An index is not less than the length of the array.
+    IndexOutOfBounds { index: usize, len: usize },
+    /// More dimensions were requested than the vectors have items.
+    TooManyDimensions { dims: usize, width: usize },
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SplitError::LengthMismatch {
+                dim,
+                expected,
+                found,
+            } => write!(
+                f,
+                "dimension {dim} has {found} items, but {expected} were expected"
+            ),
+            SplitError::MissingComponent { index, dim } => {
+                write!(f, "vector {index} has no item for dimension {dim}")
+            }
+            SplitError::IndexOutOfBounds { index, len } => {
+                write!(f, "index {index} is too large for array of length {len}")
+            }
+            SplitError::TooManyDimensions { dims, width } => {
+                write!(
+                    f,
+                    "cannot split {dims} dimensions from vectors of {width} items"
+                )
+            }
+        }
+    }
+}
+
+impl Error for SplitError {}
@@ -0,0 +1,169 @@
+//! Histograms of split dimensions. They are counted straight from the
+//! accessors, so no column has to be copied out first.
+
+use alloc::{borrow::Cow, vec, vec::Vec};
+
+use crate::{
+    stats::{Number, ReduceAccessor},
+    *,
+};
+
+/// How the items are divided into bins.
+#[derive(Debug, Clone, PartialEq)]
+enum Bins<T> {
+    Edges(Vec<T>),
+    Count { count: usize, range: Option<(T, T)> },
+}
+
+/// Counts how many items of an accessor fall into each bin. Bin `i` holds the
+/// items in `[edges[i], edges[i + 1])`, except for the last one, which also
+/// holds the items equal to the last edge. Items outside of all bins, and
+/// NaNs, are not counted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram<T> {
+    bins: Bins<T>,
+}
+
+impl<T: Number> Histogram<T> {
+    /// Creates a histogram with the given bin edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than two edges, or if they are not sorted.
+    pub fn with_edges(edges: impl Into<Vec<T>>) -> Self {
+        let edges = edges.into();
+        assert!(edges.len() >= 2, "there must be at least one bin.");
+        assert!(
+            edges.windows(2).all(|pair| pair[0] <= pair[1]),
+            "bin edges must be sorted."
+        );
+        Self {
+            bins: Bins::Edges(edges),
+        }
+    }
+
+    /// Creates a histogram with `count` bins of equal width, from the
+    /// smallest to the largest item of the accessor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is 0.
+    pub fn with_bins(count: usize) -> Self {
+        assert!(count != 0, "there must be at least one bin.");
+        Self {
+            bins: Bins::Count { count, range: None },
+        }
+    }
+
+    /// Creates a histogram with `count` bins of equal width, from `min` to
+    /// `max`. For integers, the inner edges are rounded towards zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is 0, or if `min > max`.
+    pub fn with_range(count: usize, min: T, max: T) -> Self {
+        assert!(count != 0, "there must be at least one bin.");
+        assert!(min <= max, "bin edges must be sorted.");
+        Self {
+            bins: Bins::Count {
+                count,
+                range: Some((min, max)),
+            },
+        }
+    }
+
+    /// The amount of bins.
+    pub fn len(&self) -> usize {
+        match &self.bins {
+            Bins::Edges(edges) => edges.len() - 1,
+            Bins::Count { count, .. } => *count,
+        }
+    }
+
+    /// Always false, as a histogram has at least one bin.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Gets the bin edges that are used for `accessor`. Returns `None` if the
+    /// range is taken from the items, but there are none.
+    pub fn edges<A: Accessor<T, usize> + ?Sized>(&self, accessor: &A) -> Option<Cow<'_, [T]>> {
+        match &self.bins {
+            Bins::Edges(edges) => Some(Cow::Borrowed(edges)),
+            Bins::Count { count, range } => {
+                let (min, max) = match range {
+                    Some(range) => *range,
+                    None => (accessor.min()?, accessor.max()?),
+                };
+                // Integers may overflow when subtracted or multiplied, so the edges are
+                // computed as f64. They are clamped in case rounding moves them out of the
+                // range, which would unsort them.
+                let (low, high) = (min.to_f64(), max.to_f64());
+                let mut edges = vec![min];
+                edges.extend((1..*count).map(|i| {
+                    let edge = T::from_f64(low + (high - low) * i as f64 / *count as f64);
+                    if edge < min {
+                        min
+                    } else if edge > max {
+                        max
+                    } else {
+                        edge
+                    }
+                }));
+                edges.push(max);
+                Some(Cow::Owned(edges))
+            }
+        }
+    }
+
+    /// Counts the items of `accessor` in each bin.
+    pub fn count<A: Accessor<T, usize> + ?Sized>(&self, accessor: &A) -> Vec<usize> {
+        let mut counts = vec![0; self.len()];
+        if let Some(edges) = self.edges(accessor) {
+            for i in 0..accessor.len() {
+                if let Some(bin) = find_bin(&edges, accessor[i]) {
+                    counts[bin] += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Adds up the weights of the items of `accessor` in each bin. The
+    /// weights are usually another dimension of the same array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights` does not have the same length as `accessor`.
+    pub fn count_weighted<A, W>(&self, accessor: &A, weights: &W) -> Vec<T>
+    where
+        A: Accessor<T, usize> + ?Sized,
+        W: Accessor<T, usize> + ?Sized,
+    {
+        assert_eq!(
+            accessor.len(),
+            weights.len(),
+            "lengths of the arrays do not match."
+        );
+        let mut sums = vec![T::ZERO; self.len()];
+        if let Some(edges) = self.edges(accessor) {
+            for i in 0..accessor.len() {
+                if let Some(bin) = find_bin(&edges, accessor[i]) {
+                    sums[bin] = sums[bin] + weights[i];
+                }
+            }
+        }
+        sums
+    }
+}
+
+/// Gets the bin that `item` falls into.
+fn find_bin<T: Number>(edges: &[T], item: T) -> Option<usize> {
+    let bins = edges.len() - 1;
+    // NaNs fail both comparisons.
+    if !(item >= edges[0] && item <= edges[bins]) {
+        return None;
+    }
+    // The last edge belongs to the last bin.
+    Some((edges.partition_point(|edge| *edge <= item) - 1).min(bins - 1))
+}
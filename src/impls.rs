@@ -1,16 +1,39 @@
+use core::ptr;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use crate::*;
 
-impl<T, const D: usize, V: Vector<T, D>> VectorArray<T, D, V, usize> for Vec<V> {
-    fn get<'a>(&'a self, index: usize) -> Option<&'a V> {
-        <[V]>::get(self, index)
+#[cfg(feature = "alloc")]
+// SAFETY: the items are gotten through Vector, which makes them distinct per dimension.
+unsafe impl<T, const D: usize, V: Vector<T, D>> VectorArray<T, D, V, usize> for Vec<V> {
+    fn len(&self) -> usize {
+        <[V]>::len(self)
+    }
+
+    unsafe fn get_item_ptr(this: *const Self, index: usize, dim: usize) -> Option<*const T> {
+        // Vec::as_ptr does not create a reference to the items, only to the Vec itself.
+        let vec = &*this;
+        if index >= vec.len() {
+            return None;
+        }
+        V::get_ptr(vec.as_ptr().add(index), dim)
     }
 
-    fn get_mut<'a>(&'a mut self, index: usize) -> Option<&'a mut V> {
-        <[V]>::get_mut(self, index)
+    unsafe fn get_item_ptr_mut(this: *mut Self, index: usize, dim: usize) -> Option<*mut T> {
+        // Vec::as_mut_ptr does not create a reference to the items, only to the Vec itself.
+        let vec = &mut *this;
+        if index >= vec.len() {
+            return None;
+        }
+        V::get_ptr_mut(vec.as_mut_ptr().add(index), dim)
     }
 }
 
-impl<T, const D: usize, V: RawVector<T, D>> SizedVectorArray<T, D, V, usize> for Vec<V> {
+#[cfg(feature = "alloc")]
+// SAFETY: the items of a Vec are stored one after the other.
+unsafe impl<T, const D: usize, V: RawVector<T, D>> SizedVectorArray<T, D, V, usize> for Vec<V> {
     fn ptr(&self) -> *const V {
         <[V]>::as_ptr(self)
     }
@@ -18,28 +41,33 @@ impl<T, const D: usize, V: RawVector<T, D>> SizedVectorArray<T, D, V, usize> for
     fn ptr_mut(&mut self) -> *mut V {
         <[V]>::as_mut_ptr(self)
     }
+}
 
+// SAFETY: see Vec.
+unsafe impl<T: ?Sized, const D: usize, V: Vector<T, D>> VectorArray<T, D, V, usize> for &mut [V] {
     fn len(&self) -> usize {
         <[V]>::len(self)
     }
 
-    #[inline]
-    fn convert_index(&self, index: usize) -> usize {
-        index
-    }
-}
-
-impl<T: ?Sized, const D: usize, V: Vector<T, D>> VectorArray<T, D, V, usize> for &mut [V] {
-    fn get<'a>(&'a self, index: usize) -> Option<&'a V> {
-        <[V]>::get(self, index)
+    unsafe fn get_item_ptr(this: *const Self, index: usize, dim: usize) -> Option<*const T> {
+        let slice = ptr::addr_of!(**this);
+        if index >= slice.len() {
+            return None;
+        }
+        V::get_ptr((slice as *const V).add(index), dim)
     }
 
-    fn get_mut<'a>(&'a mut self, index: usize) -> Option<&'a mut V> {
-        <[V]>::get_mut(self, index)
+    unsafe fn get_item_ptr_mut(this: *mut Self, index: usize, dim: usize) -> Option<*mut T> {
+        let slice = ptr::addr_of_mut!(**this);
+        if index >= slice.len() {
+            return None;
+        }
+        V::get_ptr_mut((slice as *mut V).add(index), dim)
     }
 }
 
-impl<T, const D: usize, V: RawVector<T, D>> SizedVectorArray<T, D, V, usize> for &mut [V] {
+// SAFETY: see Vec.
+unsafe impl<T, const D: usize, V: RawVector<T, D>> SizedVectorArray<T, D, V, usize> for &mut [V] {
     fn ptr(&self) -> *const V {
         <[V]>::as_ptr(self)
     }
@@ -47,30 +75,33 @@ impl<T, const D: usize, V: RawVector<T, D>> SizedVectorArray<T, D, V, usize> for
     fn ptr_mut(&mut self) -> *mut V {
         <[V]>::as_mut_ptr(self)
     }
-
-    fn len(&self) -> usize {
-        <[V]>::len(self)
-    }
-
-    #[inline]
-    fn convert_index(&self, index: usize) -> usize {
-        index
-    }
 }
 
-impl<T: ?Sized, const D: usize, V: Vector<T, D>, const A: usize> VectorArray<T, D, V, usize>
+// SAFETY: see Vec.
+unsafe impl<T: ?Sized, const D: usize, V: Vector<T, D>, const A: usize> VectorArray<T, D, V, usize>
     for [V; A]
 {
-    fn get<'a>(&'a self, index: usize) -> Option<&'a V> {
-        <[V]>::get(self, index)
+    fn len(&self) -> usize {
+        A
     }
 
-    fn get_mut<'a>(&'a mut self, index: usize) -> Option<&'a mut V> {
-        <[V]>::get_mut(self, index)
+    unsafe fn get_item_ptr(this: *const Self, index: usize, dim: usize) -> Option<*const T> {
+        if index >= A {
+            return None;
+        }
+        V::get_ptr((this as *const V).add(index), dim)
+    }
+
+    unsafe fn get_item_ptr_mut(this: *mut Self, index: usize, dim: usize) -> Option<*mut T> {
+        if index >= A {
+            return None;
+        }
+        V::get_ptr_mut((this as *mut V).add(index), dim)
     }
 }
 
-impl<T, const D: usize, V: RawVector<T, D>, const A: usize> SizedVectorArray<T, D, V, usize>
+// SAFETY: see Vec.
+unsafe impl<T, const D: usize, V: RawVector<T, D>, const A: usize> SizedVectorArray<T, D, V, usize>
     for [V; A]
 {
     fn ptr(&self) -> *const V {
@@ -80,20 +111,11 @@ impl<T, const D: usize, V: RawVector<T, D>, const A: usize> SizedVectorArray<T,
     fn ptr_mut(&mut self) -> *mut V {
         <[V]>::as_mut_ptr(self)
     }
-
-    fn len(&self) -> usize {
-        A
-    }
-
-    #[inline]
-    fn convert_index(&self, index: usize) -> usize {
-        index
-    }
 }
 
 macro_rules! impl_tuple {
     ($type:tt, $amount:expr; $($item:tt ,)*) => {
-        impl<T: Sized> Vector<T, $amount> for $type {
+        unsafe impl<T: Sized> Vector<T, $amount> for $type {
             fn get<'a>(&'a self, i: usize) -> Option<&'a T> {
                 match i {
                     $(
@@ -111,8 +133,29 @@ macro_rules! impl_tuple {
                     _ => None,
                 }
             }
+
+            unsafe fn get_ptr(this: *const Self, i: usize) -> Option<*const T> {
+                match i {
+                    $(
+                        $item => Some(ptr::addr_of!((*this).$item)),
+                    )*
+                    _ => None,
+                }
+            }
+
+            unsafe fn get_ptr_mut(this: *mut Self, i: usize) -> Option<*mut T> {
+                match i {
+                    $(
+                        $item => Some(ptr::addr_of_mut!((*this).$item)),
+                    )*
+                    _ => None,
+                }
+            }
+        }
+        // Tuples are repr(Rust), so the fields may be reordered. Use their real offsets.
+        unsafe impl<T: Sized> RawVector<T, $amount> for $type {
+            const OFFSETS: [usize; $amount] = [$(mem::offset_of!($type, $item),)*];
         }
-        unsafe impl<T: Sized> RawVector<T, $amount> for $type {}
     };
 }
 
@@ -128,7 +171,8 @@ impl_tuple!((T, T, T, T, T, T, T, T, T      ),  9; 0, 1, 2, 3, 4, 5, 6, 7, 8,);
 impl_tuple!((T, T, T, T, T, T, T, T, T, T   ), 10; 0, 1, 2, 3, 4, 5, 6, 7, 8, 9,);
 impl_tuple!((T, T, T, T, T, T, T, T, T, T, T), 11; 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10,);
 
-impl<T, const D: usize> Vector<T, D> for [T; D] {
+// SAFETY: every index below D is a distinct item of the array.
+unsafe impl<T, const D: usize> Vector<T, D> for [T; D] {
     fn get<'a>(&'a self, index: usize) -> Option<&'a T> {
         <[T]>::get(self, index)
     }
@@ -136,12 +180,22 @@ impl<T, const D: usize> Vector<T, D> for [T; D] {
     fn get_mut<'a>(&'a mut self, index: usize) -> Option<&'a mut T> {
         <[T]>::get_mut(self, index)
     }
+
+    unsafe fn get_ptr(this: *const Self, index: usize) -> Option<*const T> {
+        (index < D).then(|| (this as *const T).add(index))
+    }
+
+    unsafe fn get_ptr_mut(this: *mut Self, index: usize) -> Option<*mut T> {
+        (index < D).then(|| (this as *mut T).add(index))
+    }
 }
 
 unsafe impl<T, const D: usize> RawVector<T, D> for [T; D] {}
 
+#[cfg(feature = "alloc")]
 /// no [`RawVector`] here, as Vec is not aligned properly for that.
-impl<T, const D: usize> Vector<T, D> for Vec<T> {
+// SAFETY: every index below len is a distinct item of the Vec.
+unsafe impl<T, const D: usize> Vector<T, D> for Vec<T> {
     fn get<'a>(&'a self, index: usize) -> Option<&'a T> {
         <[T]>::get(self, index)
     }
@@ -149,4 +203,14 @@ impl<T, const D: usize> Vector<T, D> for Vec<T> {
     fn get_mut<'a>(&'a mut self, index: usize) -> Option<&'a mut T> {
         <[T]>::get_mut(self, index)
     }
+
+    unsafe fn get_ptr(this: *const Self, index: usize) -> Option<*const T> {
+        let vec = &*this;
+        (index < vec.len()).then(|| vec.as_ptr().add(index))
+    }
+
+    unsafe fn get_ptr_mut(this: *mut Self, index: usize) -> Option<*mut T> {
+        let vec = &mut *this;
+        (index < vec.len()).then(|| vec.as_mut_ptr().add(index))
+    }
 }
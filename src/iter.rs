@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::{iter::FusedIterator, marker::PhantomData};
 
 use crate::*;
 
@@ -7,21 +7,59 @@ pub struct AccessorIter<'a: 'b, 'b, T: ?Sized, A: Accessor<T, usize> + 'a> {
     pub(crate) phantom_t: PhantomData<&'b T>,
     pub(crate) safe_accessor: &'a A,
     pub(crate) idx: usize,
+    pub(crate) end: usize,
 }
 
 impl<'a: 'b, 'b, T: ?Sized, A: Accessor<T, usize> + 'a> Iterator for AccessorIter<'a, 'b, T, A> {
     type Item = &'b T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.safe_accessor.get((self.idx, self.idx += 1).0)
+        if self.idx >= self.end {
+            return None;
+        }
+        // Indexing panics on a missing item, so the iterator cannot end early.
+        Some(&self.safe_accessor[(self.idx, self.idx += 1).0])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.idx = self.idx.saturating_add(n).min(self.end);
+        self.next()
+    }
+}
+
+impl<'a: 'b, 'b, T: ?Sized, A: Accessor<T, usize> + 'a> DoubleEndedIterator
+    for AccessorIter<'a, 'b, T, A>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(&self.safe_accessor[self.end])
     }
 }
 
+impl<'a: 'b, 'b, T: ?Sized, A: Accessor<T, usize> + 'a> ExactSizeIterator
+    for AccessorIter<'a, 'b, T, A>
+{
+}
+
+impl<'a: 'b, 'b, T: ?Sized, A: Accessor<T, usize> + 'a> FusedIterator
+    for AccessorIter<'a, 'b, T, A>
+{
+}
+
 /// An iterator over a mutable [`Accessor`].
 pub struct AccessorIterMut<'a: 'b, 'b, T: ?Sized, A: AccessorMut<T, usize> + 'a> {
     pub(crate) phantom_t: PhantomData<&'b T>,
     pub(crate) safe_accessor: &'a mut A,
     pub(crate) idx: usize,
+    pub(crate) end: usize,
 }
 
 impl<'a: 'b, 'b, T: ?Sized, A: AccessorMut<T, usize> + 'a> Iterator
@@ -30,8 +68,48 @@ impl<'a: 'b, 'b, T: ?Sized, A: AccessorMut<T, usize> + 'a> Iterator
     type Item = &'b mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.end {
+            return None;
+        }
+        // Like in AccessorIter, a missing item panics.
+        let item: *mut T = &mut self.safe_accessor[(self.idx, self.idx += 1).0];
         // SAFETY: &'c T -> &'b T
-        // Guaranteed to be valid: 'b outlives T and the accessor.
-        unsafe { mem::transmute(self.safe_accessor.get_mut((self.idx, self.idx += 1).0)) }
+        // Guaranteed to be valid: 'b outlives T and the accessor, and every index is only handed
+        // out once.
+        Some(unsafe { &mut *item })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.idx = self.idx.saturating_add(n).min(self.end);
+        self.next()
+    }
+}
+
+impl<'a: 'b, 'b, T: ?Sized, A: AccessorMut<T, usize> + 'a> DoubleEndedIterator
+    for AccessorIterMut<'a, 'b, T, A>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        let item: *mut T = &mut self.safe_accessor[self.end];
+        // SAFETY: see next.
+        Some(unsafe { &mut *item })
+    }
+}
+
+impl<'a: 'b, 'b, T: ?Sized, A: AccessorMut<T, usize> + 'a> ExactSizeIterator
+    for AccessorIterMut<'a, 'b, T, A>
+{
+}
+
+impl<'a: 'b, 'b, T: ?Sized, A: AccessorMut<T, usize> + 'a> FusedIterator
+    for AccessorIterMut<'a, 'b, T, A>
+{
 }
@@ -1,87 +1,607 @@
-#![allow(clippy::needless_lifetimes)]
+#![cfg_attr(not(test), no_std)]
+#![allow(clippy::needless_lifetimes, clippy::type_complexity)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod accessors;
+pub mod chunks;
+pub mod columns;
+#[cfg(feature = "alloc")]
+pub mod dyn_array;
+pub mod error;
 pub mod fast_accessor;
+#[cfg(feature = "alloc")]
+pub mod histogram;
 mod impls;
 mod iter;
+#[cfg(test)]
+mod miri_test;
+#[cfg(any(feature = "rayon", test))]
+pub mod par_iter;
 pub mod safe_accessor;
+#[cfg(feature = "alloc")]
+pub mod soa_vec;
+pub mod sort;
+pub mod stats;
+pub mod strided_slice;
+
+use core::{
+    mem,
+    ops::{Bound, RangeBounds},
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
-use std::mem;
+#[cfg(feature = "derive")]
+pub use vec_split_derive::{NamedVector, RawVector, Vector};
+
+// Lets the derive macros refer to this crate by name in tests.
+#[cfg(test)]
+extern crate self as vec_split;
 
 use accessors::*;
+use error::SplitError;
 use fast_accessor::*;
 use safe_accessor::*;
+#[cfg(feature = "alloc")]
+use stats::QuantileAccessor;
+use stats::{Float, Number};
+use strided_slice::{StridedSlice, StridedSliceMut};
 
 /// Trait to be implemented for all types that are vector-like. For example,
-/// arrays, tuples, vecs, etc.
-pub trait Vector<T: ?Sized, const D: usize> {
+/// arrays, tuples, vecs, etc. Usually derived.
+///
+/// # Safety
+///
+/// [`Vector::get_ptr`] and [`Vector::get_ptr_mut`] MUST either return `None`
+/// or a pointer to a valid, aligned `T` inside the vector that `this` points
+/// to, which is the same item [`Vector::get`] returns. Pointers for
+/// different `i` MUST NOT overlap, and getting them MUST NOT create a
+/// reference to the whole vector or to other items. The split accessors turn
+/// these pointers into references.
+pub unsafe trait Vector<T: ?Sized, const D: usize> {
     fn get<'a>(&'a self, i: usize) -> Option<&'a T>;
     fn get_mut<'a>(&'a mut self, i: usize) -> Option<&'a mut T>;
+
+    /// Gets a pointer to an item of the vector without creating a reference
+    /// to the whole vector, so that references to its other items stay valid.
+    ///
+    /// # Safety
+    ///
+    /// `this` MUST point to a valid vector.
+    unsafe fn get_ptr(this: *const Self, i: usize) -> Option<*const T>;
+    /// Gets a mutable pointer to an item of the vector without creating a
+    /// reference to the whole vector, so that references to its other items
+    /// stay valid.
+    ///
+    /// # Safety
+    ///
+    /// `this` MUST point to a valid vector, and be valid for writes.
+    unsafe fn get_ptr_mut(this: *mut Self, i: usize) -> Option<*mut T>;
+}
+
+/// Trait for vectors whose dimensions have names. Its split struct has one
+/// field per dimension, so that split arrays can be accessed by name instead
+/// of by position. Usually derived, which will create a struct called
+/// `<Name>Split`.
+pub trait NamedVector<T: ?Sized, const D: usize>: Vector<T, D> {
+    /// The struct holding one `A` per dimension.
+    type Split<A>;
+
+    /// Puts the accessors for each dimension into the split struct.
+    fn name_split<A>(dims: [A; D]) -> Self::Split<A>;
 }
 
 /// Trait to be implemented for all types that are arrays of some sort and
-/// contain only vectors.
-pub trait VectorArray<T: ?Sized, const D: usize, V: Vector<T, D>, I>: Sized {
-    fn get<'a>(&'a self, index: I) -> Option<&'a V>;
-    fn get_mut<'a>(&'a mut self, index: I) -> Option<&'a mut V>;
+/// contain only vectors. The vectors do not have to be stored as `V`, as long
+/// as every item can be accessed on its own. `SoaVec`, for example, stores
+/// one Vec per dimension.
+///
+/// # Safety
+///
+/// [`VectorArray::get_item_ptr`] and [`VectorArray::get_item_ptr_mut`] MUST
+/// either return `None` or a pointer to a valid, aligned `T` inside the array
+/// that `this` points to, and MUST return `None` if `index` is not less than
+/// [`VectorArray::len`]. Both MUST return the same item for the same
+/// `(index, dim)` pair every time. Pointers for different pairs MUST NOT
+/// overlap, and getting them MUST NOT create a reference to any item, so that
+/// the accessors of other dimensions stay valid. The split accessors turn
+/// these pointers into references.
+pub unsafe trait VectorArray<T: ?Sized, const D: usize, V: Vector<T, D>, I>: Sized {
+    /// The amount of vectors in the array.
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets a pointer to one item of a vector in the array without creating a
+    /// reference to the whole array, so that references to other items stay
+    /// valid.
+    ///
+    /// # Safety
+    ///
+    /// `this` MUST point to a valid array.
+    unsafe fn get_item_ptr(this: *const Self, index: I, dim: usize) -> Option<*const T>;
+    /// Gets a mutable pointer to one item of a vector in the array without
+    /// creating a reference to the whole array, so that references to other
+    /// items stay valid.
+    ///
+    /// # Safety
+    ///
+    /// `this` MUST point to a valid array, and be valid for writes.
+    unsafe fn get_item_ptr_mut(this: *mut Self, index: I, dim: usize) -> Option<*mut T>;
+
+    /// Gets one item of the vector at `index`.
+    fn get_item<'a>(&'a self, index: I, dim: usize) -> Option<&'a T> {
+        // SAFETY: self is a valid reference.
+        unsafe { Self::get_item_ptr(self, index, dim).map(|x| &*x) }
+    }
+
+    /// Gets one item of the vector at `index` mutably.
+    fn get_item_mut<'a>(&'a mut self, index: I, dim: usize) -> Option<&'a mut T> {
+        // SAFETY: self is a valid mutable reference.
+        unsafe { Self::get_item_ptr_mut(self, index, dim).map(|x| &mut *x) }
+    }
 
     fn vec_split_safe<'a>(&'a self) -> [SafeAccessor<'a, T, D, V, I, Self>; D] {
-        let mut array = Vec::new();
-        for i in 0..D {
-            array.push(SafeAccessor::new(self, i));
-        }
-        let mut array = array.into_iter();
-        [0; D].map(|_| array.next().unwrap())
+        let len = self.len();
+        core::array::from_fn(|i| SafeAccessor::new(self, i, len))
     }
 
     fn vec_split_safe_mut<'a>(&'a mut self) -> [SafeAccessorMut<'a, T, D, V, I, Self>; D] {
-        let mut array = Vec::new();
+        let len = self.len();
+        let ptr = self as *mut Self;
         // SAFETY: SafeAccessorMut will ONLY access one dimension, and all other parts of the
-        // array will be left alone. This means one part of RAM will only be accessible once.
+        // array will be left alone. It only goes through raw pointers, so no two accessors
+        // ever hold a reference to the same part of RAM.
+        core::array::from_fn(|i| unsafe { SafeAccessorMut::new(ptr, i, len) })
+    }
+
+    /// Like [`Self::vec_split_safe`], but first checks that every vector has
+    /// an item for every dimension, so that the accessors only return `None`
+    /// for indices that are out of bounds. This is useful for jagged arrays,
+    /// like `Vec<Vec<T>>`.
+    fn vec_split_checked<'a>(
+        &'a self,
+    ) -> Result<[SafeAccessor<'a, T, D, V, I, Self>; D], SplitError>
+    where
+        I: TryFrom<usize>,
+    {
+        for index in 0..self.len() {
+            for dim in 0..D {
+                if array_index(index)
+                    .and_then(|i| self.get_item(i, dim))
+                    .is_none()
+                {
+                    return Err(SplitError::MissingComponent { index, dim });
+                }
+            }
+        }
+        Ok(self.vec_split_safe())
+    }
+
+    /// Like [`Self::vec_split_checked`], but the accessors are mutable.
+    fn vec_split_checked_mut<'a>(
+        &'a mut self,
+    ) -> Result<[SafeAccessorMut<'a, T, D, V, I, Self>; D], SplitError>
+    where
+        I: TryFrom<usize>,
+    {
+        self.vec_split_checked()?;
+        Ok(self.vec_split_safe_mut())
+    }
+
+    /// Like [`Self::vec_split_safe`], but only returns the accessors for the
+    /// given dimensions, in the given order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dimension is not less than `D`.
+    fn vec_split_safe_dims<'a, const K: usize>(
+        &'a self,
+        dims: [usize; K],
+    ) -> [SafeAccessor<'a, T, D, V, I, Self>; K] {
+        check_dims::<D, K>(&dims, false);
+        let len = self.len();
+        dims.map(|dim| SafeAccessor::new(self, dim, len))
+    }
+
+    /// Like [`Self::vec_split_safe_mut`], but only returns the accessors for
+    /// the given dimensions, in the given order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dimension is not less than `D`, or if it is given twice.
+    fn vec_split_safe_dims_mut<'a, const K: usize>(
+        &'a mut self,
+        dims: [usize; K],
+    ) -> [SafeAccessorMut<'a, T, D, V, I, Self>; K] {
+        check_dims::<D, K>(&dims, true);
+        let len = self.len();
+        let ptr = self as *mut Self;
+        // SAFETY: the dimensions are distinct, so see vec_split_safe_mut.
+        dims.map(|dim| unsafe { SafeAccessorMut::new(ptr, dim, len) })
+    }
+
+    /// Splits the dimensions in `mut_dims` mutably, and the ones in
+    /// `shared_dims` immutably, so that some dimensions can be written while
+    /// others are read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dimension is not less than `D`, or if a mutable dimension
+    /// is given twice or also given as a shared one.
+    fn vec_split_safe_mixed<'a, const K: usize, const S: usize>(
+        &'a mut self,
+        mut_dims: [usize; K],
+        shared_dims: [usize; S],
+    ) -> (
+        [SafeAccessorMut<'a, T, D, V, I, Self>; K],
+        [SafeAccessor<'a, T, D, V, I, Aliased<Self>>; S],
+    ) {
+        check_mixed_dims::<D, K, S>(&mut_dims, &shared_dims);
+        let len = self.len();
+        let ptr = self as *mut Self;
+        // SAFETY: no dimension is both mutable and shared, and mutable dimensions are distinct,
+        // so see vec_split_safe_mut. The shared accessors never write.
         unsafe {
-            for i in 0..D {
-                array.push(SafeAccessorMut::new(&mut *(self as *mut _), i));
+            (
+                mut_dims.map(|dim| SafeAccessorMut::new(ptr, dim, len)),
+                shared_dims
+                    .map(|dim| SafeAccessor::from_ptr(ptr as *const Aliased<Self>, dim, len)),
+            )
+        }
+    }
+
+    /// Like [`Self::vec_split_safe`], but returns the accessors in the split
+    /// struct of the vector, so that they can be accessed by name.
+    fn vec_split_named_safe<'a>(&'a self) -> V::Split<SafeAccessor<'a, T, D, V, I, Self>>
+    where
+        V: NamedVector<T, D>,
+    {
+        V::name_split(self.vec_split_safe())
+    }
+
+    /// Like [`Self::vec_split_safe_mut`], but returns the accessors in the
+    /// split struct of the vector, so that they can be accessed by name.
+    fn vec_split_named_safe_mut<'a>(&'a mut self) -> V::Split<SafeAccessorMut<'a, T, D, V, I, Self>>
+    where
+        V: NamedVector<T, D>,
+    {
+        V::name_split(self.vec_split_safe_mut())
+    }
+
+    /// Adds up every dimension in one pass over the array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a vector has no item for a dimension.
+    fn per_dim_sum(&self) -> [T; D]
+    where
+        T: Number,
+        I: TryFrom<usize>,
+    {
+        let mut sums = [T::ZERO; D];
+        for index in 0..self.len() {
+            for (dim, sum) in sums.iter_mut().enumerate() {
+                *sum = *sum + *stats::expect_item(self, index, dim);
             }
         }
-        let mut array = array.into_iter();
-        [0; D].map(|_| array.next().unwrap())
+        sums
+    }
+
+    /// Gets the smallest item of every dimension in one pass over the array,
+    /// which is the lower corner of the bounding box. Returns `None` if the
+    /// array is empty. NaNs are ignored, unless every item of a dimension is
+    /// NaN.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a vector has no item for a dimension.
+    fn per_dim_min(&self) -> Option<[T; D]>
+    where
+        T: Number,
+        I: TryFrom<usize>,
+    {
+        stats::per_dim_best(self, stats::is_less)
+    }
+
+    /// Gets the largest item of every dimension in one pass over the array,
+    /// like [`Self::per_dim_min`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a vector has no item for a dimension.
+    fn per_dim_max(&self) -> Option<[T; D]>
+    where
+        T: Number,
+        I: TryFrom<usize>,
+    {
+        stats::per_dim_best(self, stats::is_greater)
+    }
+
+    /// Gets the `q`-quantile of every dimension, see
+    /// [`QuantileAccessor::quantile`].
+    /// Returns `None` if a dimension has no items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is not between 0 and 1, or if a vector has no item for a
+    /// dimension.
+    #[cfg(feature = "alloc")]
+    fn per_dim_quantile(&self, q: f64) -> Option<[T; D]>
+    where
+        T: Float,
+        I: TryFrom<usize>,
+    {
+        let quantiles = self.vec_split_safe().map(|dim| dim.quantile(q));
+        quantiles
+            .iter()
+            .all(Option::is_some)
+            .then(|| quantiles.map(Option::unwrap))
+    }
+
+    /// Gets the median of every dimension, like [`Self::per_dim_quantile`]
+    /// with 0.5.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a vector has no item for a dimension.
+    #[cfg(feature = "alloc")]
+    fn per_dim_median(&self) -> Option<[T; D]>
+    where
+        T: Float,
+        I: TryFrom<usize>,
+    {
+        self.per_dim_quantile(0.5)
+    }
+
+    /// Counts the items of every dimension in the bins of `histogram`. A bin
+    /// count without a range uses a different range for every dimension.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a vector has no item for a dimension.
+    #[cfg(feature = "alloc")]
+    fn per_dim_histogram(&self, histogram: &histogram::Histogram<T>) -> [Vec<usize>; D]
+    where
+        T: Number,
+        I: TryFrom<usize>,
+    {
+        self.vec_split_safe().map(|dim| histogram.count(&dim))
+    }
+
+    /// Gets the population covariance of every pair of dimensions, see
+    /// [`stats::covariance`].
+    fn covariance(&self) -> [[T; D]; D]
+    where
+        T: Float,
+        I: TryFrom<usize>,
+    {
+        stats::covariance(&self.vec_split_safe())
+    }
+
+    /// Gets the Pearson correlation of every pair of dimensions, see
+    /// [`stats::correlation`].
+    fn correlation(&self) -> [[T; D]; D]
+    where
+        T: Float,
+        I: TryFrom<usize>,
+    {
+        stats::correlation(&self.vec_split_safe())
+    }
+
+    /// Clones every dimension of the vectors into its own Vec.
+    #[cfg(feature = "alloc")]
+    fn to_columns(&self) -> [Vec<T>; D]
+    where
+        T: Clone + Sized,
+        I: TryFrom<usize>,
+    {
+        let len = self.len();
+        let mut columns = [(); D].map(|_| Vec::with_capacity(len));
+        for (column, dim) in columns.iter_mut().zip(self.vec_split_safe()) {
+            column.extend((0..len).map(|index| dim[index].clone()));
+        }
+        columns
     }
 }
 
+/// Converts a position in an array into the array's index type. Returns
+/// `None` if it does not fit, in which case there is no vector at it either.
+pub(crate) fn array_index<I: TryFrom<usize>>(index: usize) -> Option<I> {
+    I::try_from(index).ok()
+}
+
 /// Trait to be implemented for all types that are arrays of some sort, contain
 /// only vectors, **and have a way to access their internal pointers**. Used to
 /// allow the use of [`FastAccessor`]s.
-pub trait SizedVectorArray<T: Sized, const D: usize, V: RawVector<T, D>, I>:
+///
+/// # Safety
+///
+/// [`SizedVectorArray::ptr`] MUST point to [`VectorArray::len`] valid `V`s
+/// that are stored one after the other, and that are the same vectors the
+/// [`VectorArray`] impl accesses. [`SizedVectorArray::ptr_mut`] MUST do the
+/// same, and its pointer MUST also be valid for writes. The fast accessors
+/// are built straight from these pointers.
+pub unsafe trait SizedVectorArray<T: Sized, const D: usize, V: RawVector<T, D>, I>:
     VectorArray<T, D, V, I>
 {
     fn ptr(&self) -> *const V;
     fn ptr_mut(&mut self) -> *mut V;
-    fn len(&self) -> usize;
-    fn is_empty(&self) -> bool {
-        self.len() == 0
+
+    fn vec_split_fast<'a>(&'a self) -> [FastAccessor<'a, T>; D] {
+        let ptr = self.ptr() as *const T;
+        let len = self.len();
+        // SAFETY: implementing RawVector requires memory layout to be sound for this
+        // operation: every dimension MUST be a T at its offset in V::OFFSETS
+        core::array::from_fn(|i| unsafe {
+            StridedSlice::from_raw_parts(
+                ptr.wrapping_byte_add(V::OFFSETS[i]),
+                len,
+                mem::size_of::<V>(),
+            )
+        })
     }
-    /// Please make this inline for speed
-    fn convert_index(&self, index: I) -> usize;
 
-    fn vec_split_fast<'a>(&'a self) -> [FastAccessor<'a, T, D, V, I, Self>; D] {
-        let mut array = Vec::new();
-        for i in 0..D {
-            array.push(FastAccessor::new(self, i));
-        }
-        let mut array = array.into_iter();
-        [0; D].map(|_| array.next().unwrap())
+    fn vec_split_fast_mut<'a>(&'a mut self) -> [FastAccessorMut<'a, T>; D] {
+        let len = self.len();
+        let ptr = self.ptr_mut() as *mut T;
+        // SAFETY: see vec_split_fast. Each StridedSliceMut will ONLY access one dimension,
+        // and all other parts of the array will be left alone. This means one part of RAM
+        // will only be accessible once.
+        core::array::from_fn(|i| unsafe {
+            StridedSliceMut::from_raw_parts(
+                ptr.wrapping_byte_add(V::OFFSETS[i]),
+                len,
+                mem::size_of::<V>(),
+            )
+        })
     }
 
-    fn vec_split_fast_mut<'a>(&'a mut self) -> [FastAccessorMut<'a, T, D, V, I, Self>; D] {
-        let mut array = Vec::new();
-        // SAFETY: SafeAccessorMut will ONLY access one dimension, and all other parts of the
-        // array will be left alone. This means one part of RAM will only be accessible once.
+    /// Like [`Self::vec_split_fast`], but only returns the accessors for the
+    /// given dimensions, in the given order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dimension is not less than `D`.
+    fn vec_split_fast_dims<'a, const K: usize>(
+        &'a self,
+        dims: [usize; K],
+    ) -> [FastAccessor<'a, T>; K] {
+        check_dims::<D, K>(&dims, false);
+        let ptr = self.ptr() as *const T;
+        let len = self.len();
+        // SAFETY: see vec_split_fast.
+        dims.map(|dim| unsafe {
+            StridedSlice::from_raw_parts(
+                ptr.wrapping_byte_add(V::OFFSETS[dim]),
+                len,
+                mem::size_of::<V>(),
+            )
+        })
+    }
+
+    /// Like [`Self::vec_split_fast_mut`], but only returns the accessors for
+    /// the given dimensions, in the given order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dimension is not less than `D`, or if it is given twice.
+    fn vec_split_fast_dims_mut<'a, const K: usize>(
+        &'a mut self,
+        dims: [usize; K],
+    ) -> [FastAccessorMut<'a, T>; K] {
+        check_dims::<D, K>(&dims, true);
+        let len = self.len();
+        let ptr = self.ptr_mut() as *mut T;
+        // SAFETY: the dimensions are distinct, so see vec_split_fast_mut.
+        dims.map(|dim| unsafe {
+            StridedSliceMut::from_raw_parts(
+                ptr.wrapping_byte_add(V::OFFSETS[dim]),
+                len,
+                mem::size_of::<V>(),
+            )
+        })
+    }
+
+    /// Splits the dimensions in `mut_dims` mutably, and the ones in
+    /// `shared_dims` immutably, so that some dimensions can be written while
+    /// others are read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dimension is not less than `D`, or if a mutable dimension
+    /// is given twice or also given as a shared one.
+    fn vec_split_fast_mixed<'a, const K: usize, const S: usize>(
+        &'a mut self,
+        mut_dims: [usize; K],
+        shared_dims: [usize; S],
+    ) -> ([FastAccessorMut<'a, T>; K], [FastAccessor<'a, T>; S]) {
+        check_mixed_dims::<D, K, S>(&mut_dims, &shared_dims);
+        let len = self.len();
+        let ptr = self.ptr_mut() as *mut T;
+        // SAFETY: no dimension is both mutable and shared, and mutable dimensions are distinct,
+        // so see vec_split_fast_mut. The shared accessors never write.
         unsafe {
-            for i in 0..D {
-                array.push(FastAccessorMut::new(&mut *(self as *mut _), i));
-            }
+            (
+                mut_dims.map(|dim| {
+                    StridedSliceMut::from_raw_parts(
+                        ptr.wrapping_byte_add(V::OFFSETS[dim]),
+                        len,
+                        mem::size_of::<V>(),
+                    )
+                }),
+                shared_dims.map(|dim| {
+                    StridedSlice::from_raw_parts(
+                        ptr.wrapping_byte_add(V::OFFSETS[dim]),
+                        len,
+                        mem::size_of::<V>(),
+                    )
+                }),
+            )
         }
-        let mut array = array.into_iter();
-        [0; D].map(|_| array.next().unwrap())
+    }
+
+    /// Like [`Self::vec_split_fast`], but returns the accessors in the split
+    /// struct of the vector, so that they can be accessed by name.
+    fn vec_split_named_fast<'a>(&'a self) -> V::Split<FastAccessor<'a, T>>
+    where
+        V: NamedVector<T, D>,
+    {
+        V::name_split(self.vec_split_fast())
+    }
+
+    /// Like [`Self::vec_split_fast_mut`], but returns the accessors in the
+    /// split struct of the vector, so that they can be accessed by name.
+    fn vec_split_named_fast_mut<'a>(&'a mut self) -> V::Split<FastAccessorMut<'a, T>>
+    where
+        V: NamedVector<T, D>,
+    {
+        V::name_split(self.vec_split_fast_mut())
+    }
+
+    /// Clones every dimension of the vectors into its own Vec. Unlike
+    /// [`VectorArray::to_columns`], this copies a cache-sized block of vectors
+    /// at a time without bounds checks, which is a lot faster for large
+    /// arrays.
+    #[cfg(feature = "alloc")]
+    fn to_columns_fast(&self) -> [Vec<T>; D]
+    where
+        T: Clone,
+    {
+        columns::to_columns_blocked::<T, V, D>(self.vec_split_fast())
+    }
+
+    /// Like [`VectorArray::covariance`], but uses the fast accessors.
+    fn covariance_fast(&self) -> [[T; D]; D]
+    where
+        T: Float,
+    {
+        stats::covariance(&self.vec_split_fast())
+    }
+
+    /// Like [`VectorArray::correlation`], but uses the fast accessors.
+    fn correlation_fast(&self) -> [[T; D]; D]
+    where
+        T: Float,
+    {
+        stats::correlation(&self.vec_split_fast())
+    }
+
+    /// Clones the items of one column per dimension into the vectors, a block
+    /// at a time. The columns can be slices, Vecs, or accessors, and must all
+    /// have as many items as the array.
+    fn write_columns<A: Accessor<T, usize> + ?Sized>(
+        &mut self,
+        columns: [&A; D],
+    ) -> Result<(), SplitError>
+    where
+        T: Clone,
+    {
+        columns::write_columns_blocked::<T, V, A, D>(self.vec_split_fast_mut(), columns)
     }
 }
 
@@ -90,15 +610,111 @@ pub trait SizedVectorArray<T: Sized, const D: usize, V: RawVector<T, D>, I>:
 ///
 /// # Safety
 ///
-/// MUST have no extra items before first dimension in memory, MUST
-/// not have padding between items!! This means Vec, for example, is **NOT**
-/// fit for this trait. [T; D], for example, works.
-pub unsafe trait RawVector<T: Sized, const D: usize>: Vector<T, D> {}
+/// Every dimension MUST be stored inline as a `T` at the byte offset given by
+/// [`RawVector::OFFSETS`]. With the default offsets, this means it MUST have
+/// no extra items before first dimension in memory, and MUST not have padding
+/// between items!! This means Vec, for example, is **NOT** fit for this trait.
+/// [T; D], for example, works.
+pub unsafe trait RawVector<T: Sized, const D: usize>: Vector<T, D> {
+    /// The byte offset of each dimension from the start of the vector. By
+    /// default, the dimensions are expected to be right after each other, in
+    /// order. Types whose layout is not specified, like tuples, should use
+    /// [`core::mem::offset_of`] to get the real offsets.
+    const OFFSETS: [usize; D] = {
+        let mut offsets = [0; D];
+        let mut i = 0;
+        while i < D {
+            offsets[i] = i * mem::size_of::<T>();
+            i += 1;
+        }
+        offsets
+    };
+}
+
+/// Checks that every dimension exists, and, if `distinct` is set, that none
+/// of them is given twice.
+///
+/// # Panics
+///
+/// Panics if the check fails.
+fn check_dims<const D: usize, const K: usize>(dims: &[usize; K], distinct: bool) {
+    for (i, &dim) in dims.iter().enumerate() {
+        assert!(dim < D, "dimension is too large for vector.");
+        if distinct {
+            assert!(!dims[..i].contains(&dim), "dimension was split twice.");
+        }
+    }
+}
+
+/// Checks the dimensions of a mixed split. Mutable dimensions must be
+/// distinct, and must not be shared as well.
+///
+/// # Panics
+///
+/// Panics if the check fails.
+fn check_mixed_dims<const D: usize, const K: usize, const S: usize>(
+    mut_dims: &[usize; K],
+    shared_dims: &[usize; S],
+) {
+    check_dims::<D, K>(mut_dims, true);
+    check_dims::<D, S>(shared_dims, false);
+    for dim in shared_dims {
+        assert!(!mut_dims.contains(dim), "dimension was split twice.");
+    }
+}
+
+/// Turns a range into the start and end indices of a view with `len` items.
+///
+/// # Panics
+///
+/// Panics if the range is out of bounds, or if its start is after its end.
+fn slice_range(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .expect("range start is too large for array."),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .expect("range end is too large for array."),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "range start is larger than range end.");
+    assert!(end <= len, "range end is too large for array.");
+    (start, end)
+}
 
 #[cfg(test)]
 mod test {
-    use crate::{Accessor, SizedVectorArray, VectorArray};
+    // With the derive feature, the macros are re-exported next to the traits.
+    #[cfg(feature = "derive")]
+    use crate::{NamedVector, RawVector, Vector};
+    #[cfg(not(feature = "derive"))]
+    use vec_split_derive::{NamedVector, RawVector, Vector};
 
+    #[cfg(all(feature = "alloc", not(feature = "derive")))]
+    use crate::RawVector;
+    use crate::{
+        accessors::ModifyAccessorMut, error::SplitError, sort::SortVectorArray,
+        stats::ReduceAccessor, Accessor, SizedVectorArray, VectorArray,
+    };
+    #[cfg(feature = "alloc")]
+    use crate::{
+        accessors::{CopyAccessor, CopyAccessorMut, IterateAccessor, IterateAccessorMut},
+        columns::{self, IntoColumns},
+        dyn_array::{DynVectorArray, DynVectorArrayMut, FlatArray},
+        histogram::Histogram,
+        soa_vec::SoaVec,
+        stats::{self, QuantileAccessor},
+        strided_slice::{StridedSlice, StridedSliceMut},
+        AccessorMut,
+    };
+
+    #[cfg(feature = "alloc")]
     #[test]
     fn safe_accessor_vec() {
         let vec = vec![(1, 2), (3, 4), (5, 6)];
@@ -117,6 +733,7 @@ mod test {
         }
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn fast_accessor_vec() {
         let vec = vec![(1, 2), (3, 4), (5, 6)];
@@ -134,4 +751,936 @@ mod test {
             println!();
         }
     }
+
+    #[test]
+    fn split_without_alloc() {
+        #[derive(Vector, RawVector, NamedVector, Debug, PartialEq)]
+        #[repr(C)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let mut points = [
+            Point { x: 3, y: 1 },
+            Point { x: 1, y: 2 },
+            Point { x: 2, y: 3 },
+        ];
+        let [x, y] = points.vec_split_safe();
+        assert_eq!(x.len(), 3);
+        assert_eq!(x.sum(), 6);
+        assert_eq!(y.max(), Some(3));
+        assert_eq!(
+            x.try_get(3),
+            Err(SplitError::IndexOutOfBounds { index: 3, len: 3 })
+        );
+
+        let PointSplit { mut x, mut y } = points.vec_split_named_fast_mut();
+        x.swap(0, 2);
+        y.map_in_place(|y| y * 10);
+        let mut xs = [0; 3];
+        x.copy_to_slice(&mut xs);
+        assert_eq!(xs, [2, 1, 3]);
+
+        points.sort_unstable_by_dim(0);
+        assert_eq!(
+            points,
+            [
+                Point { x: 1, y: 20 },
+                Point { x: 2, y: 10 },
+                Point { x: 3, y: 30 },
+            ]
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn custom_index_type() {
+        struct Points(Vec<[i32; 2]>);
+
+        // SAFETY: forwards to the Vec.
+        unsafe impl VectorArray<i32, 2, [i32; 2], u32> for Points {
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            unsafe fn get_item_ptr(
+                this: *const Self,
+                index: u32,
+                dim: usize,
+            ) -> Option<*const i32> {
+                VectorArray::<_, 2, _, usize>::get_item_ptr(
+                    core::ptr::addr_of!((*this).0),
+                    index as usize,
+                    dim,
+                )
+            }
+
+            unsafe fn get_item_ptr_mut(
+                this: *mut Self,
+                index: u32,
+                dim: usize,
+            ) -> Option<*mut i32> {
+                VectorArray::<_, 2, _, usize>::get_item_ptr_mut(
+                    core::ptr::addr_of_mut!((*this).0),
+                    index as usize,
+                    dim,
+                )
+            }
+        }
+
+        let mut points = Points(vec![[1, 2], [3, 4], [5, 6]]);
+        let [x, y] = points.vec_split_safe();
+        assert_eq!(x[2], 5);
+        assert_eq!(y.slice(1..).iter().copied().collect::<Vec<_>>(), vec![4, 6]);
+        let [mut x, _] = points.vec_split_safe_mut();
+        x[0] = 7;
+        assert_eq!(points.0, [[7, 2], [3, 4], [5, 6]]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn strided_slice() {
+        let mut vec = vec![[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]];
+        let [x, y, _] = vec.vec_split_fast();
+        assert_eq!(x.len(), 4);
+        assert_eq!(x.get(4), None);
+        assert_eq!(y.iter().copied().collect::<Vec<_>>(), vec![2, 5, 8, 11]);
+        assert_eq!(
+            y.iter().rev().copied().collect::<Vec<_>>(),
+            vec![11, 8, 5, 2]
+        );
+        assert_eq!(y.iter().nth(2), Some(&8));
+        let (a, b) = x.split_at(1);
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![4, 7, 10]);
+        assert_eq!(format!("{:?}", b), "[4, 7, 10]");
+
+        let [mut x, _, mut z] = vec.vec_split_fast_mut();
+        let (mut a, mut b) = z.split_at_mut(2);
+        a[0] = 0;
+        b[1] = 0;
+        for item in x.iter_mut() {
+            *item *= 2;
+        }
+        assert_eq!(vec, vec![[2, 2, 0], [8, 5, 6], [14, 8, 9], [20, 11, 0]]);
+
+        let slice = [1, 2, 3];
+        let strided = StridedSlice::from_slice(&slice);
+        assert_eq!(strided.last(), Some(&3));
+        assert_eq!(strided.stride(), std::mem::size_of::<i32>());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn tuple_offsets() {
+        let tuple = (1u16, 2u16, 3u16, 4u16);
+        let start = &tuple as *const _ as usize;
+        let real = [
+            &tuple.0 as *const _ as usize - start,
+            &tuple.1 as *const _ as usize - start,
+            &tuple.2 as *const _ as usize - start,
+            &tuple.3 as *const _ as usize - start,
+        ];
+        assert_eq!(<(u16, u16, u16, u16) as RawVector<u16, 4>>::OFFSETS, real);
+        assert_eq!(<[u16; 4] as RawVector<u16, 4>>::OFFSETS, [0, 2, 4, 6]);
+
+        let vec = vec![(1.0f32, 2.0f32), (3.0, 4.0)];
+        let [x, y] = vec.vec_split_fast();
+        assert_eq!(x.iter().copied().collect::<Vec<_>>(), vec![1.0, 3.0]);
+        assert_eq!(y.iter().copied().collect::<Vec<_>>(), vec![2.0, 4.0]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn derive() {
+        #[derive(Vector, RawVector, NamedVector, Debug, PartialEq)]
+        #[repr(C)]
+        struct Point {
+            x: f32,
+            y: f32,
+            z: f32,
+        }
+
+        #[derive(Vector)]
+        struct Pair<T>(T, T);
+
+        let mut points = vec![
+            Point {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            },
+            Point {
+                x: 4.0,
+                y: 5.0,
+                z: 6.0,
+            },
+        ];
+        let [x, y, z] = points.vec_split_fast_mut();
+        for (z, (x, y)) in z.into_iter().zip(x.iter().zip(y.iter())) {
+            *z = x + y;
+        }
+        assert_eq!(points[1].z, 9.0);
+        let PointSplit { x, mut y, .. } = points.vec_split_named_fast_mut();
+        y[0] = x[1];
+        let split = points.vec_split_named_safe();
+        assert_eq!(split.y.get(0), Some(&4.0));
+        assert_eq!(split.z.get(0), Some(&3.0));
+        assert_eq!(<Point as RawVector<f32, 3>>::OFFSETS, [0, 4, 8]);
+
+        let pairs = vec![Pair("a", "b"), Pair("c", "d")];
+        let [first, second] = pairs.vec_split_safe();
+        assert_eq!(first.get(1), Some(&"c"));
+        assert_eq!(second.get(0), Some(&"b"));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn columns() {
+        let vec = (0..3000).map(|i| [i, i * 2, i * 3]).collect::<Vec<_>>();
+        let [x, y, z] = vec.to_columns_fast();
+        assert_eq!(x, (0..3000).collect::<Vec<_>>());
+        assert_eq!(y[2999], 5998);
+        assert_eq!(z[1000], 3000);
+        assert_eq!(vec.to_columns(), [x, y, z]);
+
+        let vec = vec![(String::from("a"), String::from("b")); 2];
+        assert_eq!(vec.to_columns(), [vec!["a", "a"], vec!["b", "b"]]);
+        let [a, b] = vec.into_columns();
+        assert_eq!(a, vec!["a", "a"]);
+        assert_eq!(b, vec!["b", "b"]);
+        assert_eq!([[1, 2], [3, 4]].into_columns(), [vec![1, 3], vec![2, 4]]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn join() {
+        let x = vec![1, 2, 3];
+        let y = vec![4, 5, 6];
+        let joined: Vec<(i32, i32)> = columns::join([&x[..], &y[..]]).unwrap();
+        assert_eq!(joined, vec![(1, 4), (2, 5), (3, 6)]);
+        let joined: Vec<[i32; 2]> = columns::join_fast([&y, &x]).unwrap();
+        assert_eq!(joined, vec![[4, 1], [5, 2], [6, 3]]);
+        let moved: Vec<[i32; 2]> = columns::from_columns([x.clone(), y.clone()]).unwrap();
+        assert_eq!(moved, vec![[1, 4], [2, 5], [3, 6]]);
+
+        let mut array = [[0, 0]; 3];
+        array.write_columns([&x, &y]).unwrap();
+        assert_eq!(array, [[1, 4], [2, 5], [3, 6]]);
+        let [a, b] = moved.vec_split_fast();
+        let swapped: Vec<[i32; 2]> = columns::join([&b, &a]).unwrap();
+        assert_eq!(swapped, vec![[4, 1], [5, 2], [6, 3]]);
+
+        let short = vec![1, 2];
+        assert_eq!(
+            array.write_columns([&x, &short]),
+            Err(SplitError::LengthMismatch {
+                dim: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert!(columns::from_columns::<i32, [i32; 2], 2>([x, short]).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn soa_vec() {
+        let mut soa = SoaVec::new();
+        soa.extend([[1, 2], [3, 4]]);
+        soa.push([5, 6]);
+        soa.insert(0, [7, 8]);
+        assert_eq!(soa.len(), 4);
+        assert_eq!(soa.column(0), &[7, 1, 3, 5]);
+        assert_eq!(soa.remove(1), [1, 2]);
+        assert_eq!(soa.pop(), Some([5, 6]));
+        assert_eq!(soa.get(1), Some([&3, &4]));
+
+        let [mut x, y] = soa.vec_split_safe_mut();
+        for i in 0..2 {
+            x[i] += y[i];
+        }
+        assert_eq!(soa.columns(), [&[15, 7][..], &[8, 4][..]]);
+        assert_eq!(soa.get_item(1, 1), Some(&4));
+        assert_eq!(soa.to_columns(), [vec![15, 7], vec![8, 4]]);
+        soa.truncate(1);
+        assert_eq!(soa.into_columns(), [vec![15], vec![8]]);
+        assert!(SoaVec::from_columns([vec![1], vec![]]).is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn accessor_iter() {
+        let mut vec = vec![vec![1, 2], vec![3, 4], vec![5, 6], vec![7, 8]];
+        let [x, y] = vec.vec_split_safe();
+        assert_eq!(x.len(), 4);
+        let mut iter = x.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.nth(1), Some(&3));
+        assert_eq!(iter.next_back(), Some(&7));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(y.iter().rev().collect::<Vec<_>>(), vec![&8, &6, &4, &2]);
+        assert_eq!(x.iter().zip(y.iter()).len(), 4);
+
+        let [mut x, _] = vec.vec_split_safe_mut();
+        let mut iter = x.iter_mut();
+        *iter.next_back().unwrap() = 0;
+        *iter.nth(1).unwrap() = 0;
+        assert_eq!(iter.len(), 1);
+        let [x, _] = vec.to_columns();
+        assert_eq!(x, vec![1, 0, 5, 0]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn len() {
+        let mut vec = vec![[1, 2]; 3];
+        assert_eq!(VectorArray::len(&vec), 3);
+        assert_eq!(VectorArray::len(&[[0; 4]; 5]), 5);
+        let slice = &mut vec[1..];
+        assert_eq!(VectorArray::len(&slice), 2);
+        assert!(VectorArray::is_empty(&Vec::<(u8, u8)>::new()));
+        assert!(VectorArray::is_empty(&SoaVec::<u8, 3>::new()));
+
+        let [x, _] = vec.vec_split_safe();
+        assert_eq!(Accessor::len(&x), 3);
+        assert!(!Accessor::is_empty(&x));
+        let empty: [[u8; 2]; 0] = [];
+        let [x, _] = empty.vec_split_fast();
+        assert!(Accessor::is_empty(&x));
+        assert_eq!(Accessor::<u8, usize>::len(&vec![0u8; 7]), 7);
+        assert_eq!(Accessor::<u8, usize>::len(&[0u8; 2][..]), 2);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn slicing() {
+        let mut vec = (0..10).map(|i| [i, i * 10]).collect::<Vec<_>>();
+        let [x, y] = vec.vec_split_fast();
+        let x = x.slice(2..5);
+        assert_eq!(x.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(x.slice(1..).slice(..=0)[0], 3);
+        let (first, second) = y.split_at(8);
+        assert_eq!((first.len(), second[1]), (8, 90));
+
+        let [x, y] = vec.vec_split_safe();
+        let x = x.slice(..3);
+        assert_eq!(x.get(3), None);
+        assert_eq!(x.iter().rev().copied().collect::<Vec<_>>(), vec![2, 1, 0]);
+        let (_, second) = y.slice(5..).split_at(2);
+        assert_eq!(second.iter().copied().collect::<Vec<_>>(), vec![70, 80, 90]);
+
+        let [mut x, mut y] = vec.vec_split_fast_mut();
+        let (mut first, mut second) = x.split_at_mut(5);
+        first[0] = second[0];
+        second[4] = first[4];
+        y.slice_mut(1..3).iter_mut().for_each(|y| *y = 0);
+
+        let mut soa = vec.iter().copied().collect::<SoaVec<_, 2>>();
+        let [mut x, _] = soa.vec_split_safe_mut();
+        let (mut first, mut second) = x.split_at_mut(5);
+        std::mem::swap(&mut first[1], &mut second[1]);
+        x.slice_mut(8..)[0] = 100;
+        assert_eq!(soa.column(0), &[5, 6, 2, 3, 4, 5, 1, 7, 100, 4]);
+        assert_eq!(soa.column(1), &[0, 0, 0, 30, 40, 50, 60, 70, 80, 90]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "range end is too large for array.")]
+    fn slice_out_of_bounds() {
+        let vec = vec![[0u8; 3]; 4];
+        let [x, _, _] = vec.vec_split_safe();
+        x.slice(2..5);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn index_out_of_bounds() {
+        fn message(f: impl FnOnce()) -> String {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+            *result.unwrap_err().downcast::<String>().unwrap()
+        }
+
+        let mut vec = vec![[0u8; 3]; 4];
+        let [x, _, _] = vec.vec_split_fast();
+        assert_eq!(
+            message(|| _ = x[4]),
+            "index 4 is too large for array of length 4"
+        );
+        let [mut x, _, _] = vec.vec_split_fast_mut();
+        assert_eq!(
+            message(|| x[5] = 1),
+            "index 5 is too large for array of length 4"
+        );
+        assert_eq!(
+            message(|| x.swap(1, 4)),
+            "index 4 is too large for array of length 4"
+        );
+
+        let mut rows = [vec![1, 2], vec![3, 4]];
+        let dims = rows.vec_split_dyn(2).unwrap();
+        assert_eq!(
+            message(|| _ = dims[0][2]),
+            "index 2 is too large for array of length 2"
+        );
+        let mut dims = rows.vec_split_dyn_mut(2).unwrap();
+        assert_eq!(
+            message(|| dims[1][3] = 0),
+            "index 3 is too large for array of length 2"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn chunks() {
+        let mut vec = (0..10).map(|i| [i as f64, 0.0, 0.0]).collect::<Vec<_>>();
+        let [x, _, _] = vec.vec_split_fast();
+        let lens = x.chunks(4).map(|chunk| chunk.len()).collect::<Vec<_>>();
+        assert_eq!(lens, vec![4, 4, 2]);
+        assert_eq!(x.chunks(4).next_back().unwrap()[1], 9.0);
+        assert_eq!(x.chunks(3).nth(2).unwrap().iter().sum::<f64>(), 21.0);
+        let exact = x.chunks_exact(3);
+        assert_eq!(exact.len(), 3);
+        assert_eq!(exact.remainder()[0], 9.0);
+
+        let [x, mut y, mut z] = vec.vec_split_fast_mut();
+        std::thread::scope(|scope| {
+            for (i, mut chunk) in y.chunks_mut(3).enumerate() {
+                scope.spawn(move || chunk.iter_mut().for_each(|y| *y = i as f64));
+            }
+        });
+        let mut exact = z.chunks_exact_mut(4);
+        for mut chunk in exact.by_ref() {
+            chunk[0] = x[0] + 1.0;
+        }
+        exact.into_remainder()[1] = -1.0;
+        let [_, y, z] = vec.to_columns();
+        assert_eq!(y, [0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 3.0]);
+        assert_eq!(z, [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, -1.0]);
+
+        let mut soa = (0..7).map(|i| [i, i]).collect::<SoaVec<_, 2>>();
+        let [x, mut y] = soa.vec_split_safe_mut();
+        let sums = x
+            .chunks(2)
+            .map(|chunk| chunk.iter().sum())
+            .collect::<Vec<i32>>();
+        assert_eq!(sums, vec![1, 5, 9, 6]);
+        assert_eq!(x.chunks_exact(2).remainder().len(), 1);
+        for mut chunk in y.chunks_mut(3).rev() {
+            let first = chunk[0];
+            chunk.iter_mut().for_each(|y| *y = first);
+        }
+        assert_eq!(soa.column(1), &[0, 0, 0, 3, 3, 3, 6]);
+
+        let [x, _] = soa.vec_split_safe();
+        let sums = std::thread::scope(|scope| {
+            let handles = x
+                .chunks(3)
+                .map(|chunk| scope.spawn(move || chunk.iter().sum::<i32>()))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(sums, vec![3, 12, 6]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "chunk size must not be zero.")]
+    fn zero_chunk_size() {
+        let vec = vec![[0u8; 2]; 4];
+        let [x, _] = vec.vec_split_safe();
+        x.chunks(0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    // rayon's thread pool uses crossbeam-epoch, which Stacked Borrows rejects. The splitting
+    // of the parallel iterators is checked by miri_test::par_iter_producer_interleaved.
+    #[cfg_attr(miri, ignore)]
+    fn par_iter() {
+        use rayon::prelude::*;
+
+        let mut vec = (0..1000).map(|i| [i, 2 * i, 0]).collect::<Vec<_>>();
+        let [x, y, mut z] = vec.vec_split_fast_mut();
+        z.par_iter_mut()
+            .zip(x.par_iter())
+            .zip(y.into_par_iter())
+            .for_each(|((z, x), y)| *z = *x + *y);
+        assert!(vec.iter().all(|v| v[2] == 3 * v[0]));
+
+        let mut slice = &mut vec[10..20];
+        let [x, mut y, _] = slice.vec_split_safe_mut();
+        y.par_iter_mut().for_each(|y| *y = 0);
+        assert_eq!(x.par_iter().sum::<i32>(), 145);
+        let [_, _, z] = slice.vec_split_safe();
+        assert_eq!(z.slice(5..).into_par_iter().count(), 5);
+        assert_eq!(vec[9][1] + vec[15][1] + vec[20][1], 18 + 40);
+
+        let mut array = [(1.0, 2.0); 64];
+        let [mut x, y] = array.vec_split_safe_mut();
+        x.par_iter_mut()
+            .zip(y.par_iter())
+            .for_each(|(x, y)| *x *= *y);
+        assert_eq!(array, [(2.0, 2.0); 64]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn split_dims() {
+        let mut rgba = vec![[0.1f32, 0.2, 0.3, 1.0]; 4];
+        let [r, b] = rgba.vec_split_fast_dims([0, 2]);
+        assert_eq!((r[3], b[0]), (0.1, 0.3));
+        let [mut a, mut r] = rgba.vec_split_fast_dims_mut([3, 0]);
+        a[0] = 0.5;
+        r[1] = a[1];
+        assert_eq!(rgba[0], [0.1, 0.2, 0.3, 0.5]);
+        assert_eq!(rgba[1], [1.0, 0.2, 0.3, 1.0]);
+
+        let mut vec = vec![(1, 2, 3); 2];
+        let [z, x, z2] = vec.vec_split_safe_dims([2, 0, 2]);
+        assert_eq!((z[0], x[1], z2[1]), (3, 1, 3));
+        let [mut y] = vec.vec_split_safe_dims_mut([1]);
+        y.iter_mut().for_each(|y| *y = 0);
+        assert_eq!(vec, vec![(1, 0, 3); 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "dimension was split twice.")]
+    fn split_dims_twice() {
+        let mut vec = vec![(1, 2, 3)];
+        vec.vec_split_fast_dims_mut([1, 2, 1]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "dimension is too large for vector.")]
+    fn split_dims_too_large() {
+        let mut vec = vec![[0u8; 3]];
+        vec.vec_split_safe_dims_mut([3]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn split_mixed() {
+        let mut vec = (0..5).map(|i| [i, 10 * i, 0]).collect::<Vec<_>>();
+        let ([mut z], [x, y]) = vec.vec_split_fast_mixed([2], [0, 1]);
+        let x2 = x;
+        for (z, (x, y)) in z.iter_mut().zip(x.iter().zip(y.iter())) {
+            *z = x + y + x2[0];
+        }
+        assert_eq!(vec[4], [4, 40, 44]);
+
+        let mut soa = vec.iter().copied().collect::<SoaVec<_, 3>>();
+        let ([mut x], [y, z]) = soa.vec_split_safe_mixed([0], [1, 2]);
+        let z0 = &z[0];
+        for (x, z) in x.iter_mut().zip(z.iter()) {
+            *x = z - y[1] + z0;
+        }
+        assert_eq!(soa.column(0), &[-10, 1, 12, 23, 34]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "dimension was split twice.")]
+    fn split_mixed_overlap() {
+        let mut vec = vec![[0u8; 3]];
+        vec.vec_split_safe_mixed([0, 1], [2, 1]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn split_dyn() {
+        let mut rows = [vec![1, 2, 3], vec![4, 5, 6, 7], vec![8, 9, 10]];
+        let dims = rows.vec_split_dyn(3).unwrap();
+        assert_eq!(dims.len(), 3);
+        assert_eq!(dims[2].iter().copied().collect::<Vec<_>>(), vec![3, 6, 10]);
+        assert_eq!(
+            rows.vec_split_dyn(4).err(),
+            Some(SplitError::MissingComponent { index: 0, dim: 3 })
+        );
+        let mut dims = rows.vec_split_dyn_mut(2).unwrap();
+        let (x, y) = dims.split_at_mut(1);
+        for (x, y) in x[0].iter_mut().zip(y[0].iter()) {
+            *x += *y;
+        }
+        assert_eq!(rows[2], vec![17, 9, 10]);
+
+        let mut flat = FlatArray::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 3);
+        let dims = flat.vec_split_dyn(2).unwrap();
+        assert_eq!(dims[1].iter().copied().collect::<Vec<_>>(), vec![2.0, 5.0]);
+        for mut dim in flat.vec_split_dyn_mut(3).unwrap() {
+            dim[1] = 0.0;
+        }
+        assert_eq!(flat.into_inner(), vec![1.0, 2.0, 3.0, 0.0, 0.0, 0.0]);
+        let mut flat = FlatArray::new([0u8; 5], 2);
+        assert_eq!(
+            flat.vec_split_dyn_mut(1).err(),
+            Some(SplitError::MissingComponent { index: 2, dim: 1 })
+        );
+        assert_eq!(
+            FlatArray::new([0u8; 0], 2).vec_split_dyn(3).err(),
+            Some(SplitError::TooManyDimensions { dims: 3, width: 2 })
+        );
+
+        let buffer = [1, 2, 3, 4];
+        let flat = FlatArray::new(&buffer[..], 2);
+        let dims = flat.vec_split_dyn(2).unwrap();
+        assert_eq!(dims[1].iter().copied().collect::<Vec<_>>(), vec![2, 4]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn checked() {
+        let mut jagged = vec![vec![1, 2, 3], vec![4, 5], vec![6, 7, 8]];
+        assert_eq!(
+            VectorArray::<_, 3, _, _>::vec_split_checked(&jagged).err(),
+            Some(SplitError::MissingComponent { index: 1, dim: 2 })
+        );
+        let [x, _, z] = jagged.vec_split_safe();
+        assert_eq!(x.try_get(1), Ok(&4));
+        assert_eq!(
+            z.try_get(1),
+            Err(SplitError::MissingComponent { index: 1, dim: 2 })
+        );
+        assert_eq!(
+            z.slice(1..).try_get(0),
+            Err(SplitError::MissingComponent { index: 1, dim: 2 })
+        );
+        assert_eq!(
+            x.try_get(3),
+            Err(SplitError::IndexOutOfBounds { index: 3, len: 3 })
+        );
+
+        jagged[1].push(0);
+        let [mut x, _, _] = jagged.vec_split_checked_mut().unwrap();
+        *x.try_get_mut(2).unwrap() = 0;
+        assert!(x.try_get_mut(5).is_err());
+        assert_eq!(jagged[2], vec![0, 7, 8]);
+
+        let vec = vec![[1, 2]; 2];
+        let [x, _] = vec.vec_split_fast();
+        assert_eq!(
+            x.try_get(2),
+            Err(SplitError::IndexOutOfBounds { index: 2, len: 2 })
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "vector 1 has no item for dimension 2")]
+    fn missing_component_panic() {
+        let jagged = vec![vec![1, 2, 3], vec![4, 5]];
+        let [_, _, z] = jagged.vec_split_safe();
+        let _ = z[1];
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn bulk_copy() {
+        let mut vec = (0..6).map(|i| [i, i * i]).collect::<Vec<_>>();
+        let [x, y] = vec.vec_split_fast();
+        let mut buffer = [0; 6];
+        y.copy_to_slice(&mut buffer);
+        assert_eq!(buffer, [0, 1, 4, 9, 16, 25]);
+        assert_eq!(x.slice(4..).to_vec(), vec![4, 5]);
+
+        let [mut x, y] = vec.vec_split_fast_mut();
+        x.copy_from_slice(&[6, 5, 4, 3, 2, 1]);
+        let mut contiguous = [0; 6];
+        let mut contiguous = StridedSliceMut::from_slice(&mut contiguous);
+        assert!(contiguous.is_contiguous() && !y.is_contiguous());
+        contiguous.clone_from_accessor(&y);
+        contiguous.copy_from_slice(&contiguous.to_vec());
+        assert_eq!(contiguous.to_vec(), vec![0, 1, 4, 9, 16, 25]);
+        assert_eq!(vec[0], [6, 0]);
+
+        let mut names = vec![(String::from("a"), String::new()); 3];
+        let [first, mut second] = names.vec_split_safe_mut();
+        second.clone_from_accessor(&first);
+        second.clone_from_accessor(&vec![String::from("b"); 3]);
+        assert_eq!(first.to_vec(), vec!["a"; 3]);
+        assert_eq!(second.to_vec(), vec!["b"; 3]);
+        let mut array = [[1u8]; 2];
+        let [mut x] = array.vec_split_safe_mut();
+        x.copy_from_slice(&[7, 8]);
+        let mut out = [0; 2];
+        x.copy_to_slice(&mut out);
+        assert_eq!(out, [7, 8]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "lengths of the arrays do not match.")]
+    fn bulk_copy_mismatch() {
+        let vec = vec![[1u8, 2]; 3];
+        let [x, _] = vec.vec_split_safe();
+        x.copy_to_slice(&mut [0; 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn in_place() {
+        let mut vec = (0..5).map(|i| (i, i * 10)).collect::<Vec<_>>();
+        let [mut x, mut y] = vec.vec_split_fast_mut();
+        x.reverse();
+        x.swap(0, 1);
+        x.rotate_left(2);
+        y.rotate_right(1);
+        y.fill(7);
+        y.slice_mut(1..3).reverse();
+        assert_eq!(x.to_vec(), vec![2, 1, 0, 3, 4]);
+        x.rotate_right(5);
+        x.rotate_left(0);
+        assert_eq!(vec, vec![(2, 7), (1, 7), (0, 7), (3, 7), (4, 7)]);
+
+        let mut soa = (0..6).map(|i| [i, 0]).collect::<SoaVec<_, 2>>();
+        let [mut x, mut y] = soa.vec_split_safe_mut();
+        x.map_in_place(|x| x * 2);
+        x.reverse();
+        x.rotate_left(4);
+        x.rotate_right(1);
+        x.swap(0, 5);
+        x.swap(2, 2);
+        y.for_each_indexed(|i, y| *y = i);
+        y.slice_mut(3..).fill(9);
+        let mut counter = 0;
+        y.slice_mut(..2).fill_with(|| (counter, counter += 1).0);
+        assert_eq!(soa.column(0), &[6, 2, 0, 10, 8, 4]);
+        assert_eq!(soa.column(1), &[0, 1, 2, 9, 9, 9]);
+
+        let mut names = vec![[String::from("a"), String::from("b")]; 3];
+        let [mut first, _] = names.vec_split_safe_mut();
+        first.for_each_indexed(|i, name| name.push_str(&i.to_string()));
+        first.rotate_right(1);
+        first.swap(0, 1);
+        assert_eq!(first.to_vec(), vec!["a0", "a2", "a1"]);
+        let plain = &mut names[0][..];
+        ModifyAccessorMut::reverse(plain);
+        assert_eq!(plain, ["b", "a0"]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reductions() {
+        let points = vec![[1.0, -2.0], [4.0, f64::NAN], [-3.0, 6.0], [4.0, 0.0]];
+        let [x, y] = points.vec_split_fast();
+        assert_eq!(x.sum(), 6.0);
+        assert_eq!(x.product(), -48.0);
+        assert_eq!(x.min(), Some(-3.0));
+        assert_eq!((x.argmax(), y.argmax()), (Some(1), Some(2)));
+        assert_eq!(y.min(), Some(-2.0));
+        assert_eq!(x.mean(), Some(1.5));
+        assert_eq!(x.variance(), Some(8.25));
+        assert_eq!(x.slice(..0).mean(), None);
+
+        let counts: Vec<[u32; 2]> = vec![[3, 7], [1, 2], [5, 3]];
+        let [a, b] = counts.vec_split_safe();
+        assert_eq!((a.sum(), b.product()), (9, 42));
+        assert_eq!((a.argmin(), b.max()), (Some(1), Some(7)));
+        assert_eq!((a.mean(), a.variance()), (Some(3), Some(2)));
+
+        assert_eq!(points.per_dim_sum()[0], 6.0);
+        assert_eq!(points.per_dim_min(), Some([-3.0, -2.0]));
+        assert_eq!(points.per_dim_max(), Some([4.0, 6.0]));
+        assert_eq!(counts.per_dim_sum(), [9, 12]);
+        assert_eq!(Vec::<[i32; 2]>::new().per_dim_max(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn covariance() {
+        // The offset would cancel out all precision in a naive sum of squares.
+        let offset = 1e9;
+        let points: Vec<[f64; 3]> = [
+            [1.0, 2.0, 5.0],
+            [2.0, 4.0, 5.0],
+            [3.0, 6.0, 5.0],
+            [6.0, 0.0, 5.0],
+        ]
+        .iter()
+        .map(|point| point.map(|item| item + offset))
+        .collect();
+        let covariance = points.covariance();
+        assert_eq!(covariance, points.covariance_fast());
+        assert_eq!(covariance[0], [3.5, -2.0, 0.0]);
+        assert_eq!(covariance[1], [-2.0, 5.0, 0.0]);
+        assert_eq!(covariance[2], [0.0, 0.0, 0.0]);
+
+        let correlation = points.correlation_fast();
+        assert_eq!((correlation[0][0], correlation[1][1]), (1.0, 1.0));
+        assert!((correlation[0][1] - -2.0 / (3.5f64 * 5.0).sqrt()).abs() < 1e-12);
+        assert_eq!(correlation[0][1], correlation[1][0]);
+        assert!(correlation[2][2].is_nan());
+
+        let [x, y, _] = points.vec_split_fast();
+        assert_eq!(stats::covariance(&[y, x])[0], [5.0, -2.0]);
+        assert!(Vec::<[f32; 2]>::new().covariance()[0][0].is_nan());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn quantiles() {
+        let points = vec![
+            [4.0, 1.0],
+            [1.0, f64::NAN],
+            [3.0, 2.0],
+            [2.0, 8.0],
+            [10.0, 4.0],
+        ];
+        let [x, y] = points.vec_split_fast();
+        assert_eq!(x.median(), Some(3.0));
+        assert_eq!(y.median(), Some(3.0));
+        assert_eq!(x.quantile(0.0), Some(1.0));
+        assert_eq!(x.quantile(1.0), Some(10.0));
+        assert_eq!(x.quantile(0.875), Some(7.0));
+        assert_eq!(x.top_k(2), vec![10.0, 4.0]);
+        assert_eq!(y.top_k(10), vec![8.0, 4.0, 2.0, 1.0]);
+        assert_eq!(x.slice(..0).median(), None);
+        // The array is left as it was.
+        assert_eq!(points[0], [4.0, 1.0]);
+
+        assert_eq!(points.per_dim_median(), Some([3.0, 3.0]));
+        assert_eq!(points.per_dim_quantile(0.25), Some([2.0, 1.75]));
+        let counts = vec![[5u16], [9], [1]];
+        let [count] = counts.vec_split_safe();
+        assert_eq!(count.top_k(1), vec![9]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "q must be between 0 and 1.")]
+    fn quantile_out_of_range() {
+        [1.0f32, 2.0].quantile(1.5);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn histogram() {
+        let points = vec![
+            [0.5, 1.0],
+            [1.0, 2.0],
+            [2.5, 3.0],
+            [4.0, 4.0],
+            [f64::NAN, 5.0],
+            [7.0, 6.0],
+        ];
+        let [x, intensity] = points.vec_split_fast();
+        let edges = Histogram::with_edges([0.0, 1.0, 2.0, 4.0]);
+        assert_eq!(edges.count(&x), vec![1, 1, 2]);
+        assert_eq!(edges.count_weighted(&x, &intensity), vec![1.0, 2.0, 7.0]);
+
+        let auto = Histogram::with_bins(4);
+        assert_eq!(auto.edges(&x).unwrap()[..], [0.5, 2.125, 3.75, 5.375, 7.0]);
+        assert_eq!(auto.count(&x), vec![2, 1, 1, 1]);
+        assert_eq!(auto.count(&x.slice(..0)), vec![0; 4]);
+        assert_eq!(
+            points.per_dim_histogram(&auto),
+            [vec![2, 1, 1, 1], vec![2, 1, 1, 2]]
+        );
+
+        let counts = vec![[3u32], [0], [9], [10], [4]];
+        let [count] = counts.vec_split_safe();
+        let ranged = Histogram::with_range(2, 0, 8);
+        assert_eq!(ranged.count(&count), vec![2, 1]);
+        assert_eq!(ranged.count_weighted(&count, &count), vec![3, 4]);
+
+        // The width of these ranges does not fit into the item type.
+        let bytes = [[0u8], [49], [50], [199], [200], [255]];
+        let [byte] = bytes.vec_split_fast();
+        let ranged = Histogram::with_range(4, 0u8, 200u8);
+        assert_eq!(ranged.edges(&byte).unwrap()[..], [0, 50, 100, 150, 200]);
+        assert_eq!(ranged.count(&byte), vec![2, 1, 0, 2]);
+        let signed = [[i8::MIN], [-1], [0], [i8::MAX]];
+        let [signed] = signed.vec_split_fast();
+        let auto = Histogram::with_bins(2);
+        assert_eq!(auto.edges(&signed).unwrap()[..], [i8::MIN, 0, i8::MAX]);
+        assert_eq!(auto.count(&signed), vec![2, 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "bin edges must be sorted.")]
+    fn histogram_unsorted() {
+        Histogram::with_edges(vec![1, 0]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sort() {
+        let mut points = vec![[2, 1, 0], [1, 5, 1], [2, 0, 2], [1, 5, 3]];
+        points.sort_by_dim(0);
+        assert_eq!(points, [[1, 5, 1], [1, 5, 3], [2, 1, 0], [2, 0, 2]]);
+        points.sort_unstable_by_dims(&[1, 2]);
+        assert_eq!(points, [[2, 0, 2], [2, 1, 0], [1, 5, 1], [1, 5, 3]]);
+        points.sort_by_dims(&[0, 2]);
+        assert_eq!(points, [[1, 5, 1], [1, 5, 3], [2, 1, 0], [2, 0, 2]]);
+
+        let mut floats = [(1.5, 0.0), (f64::NAN, 1.0), (-2.0, 2.0), (1.5, -1.0)];
+        floats.sort_by_dim_total(0);
+        assert_eq!(floats.map(|point| point.1), [2.0, 0.0, -1.0, 1.0]);
+        let slice: &mut [_] = &mut floats;
+        slice.sort_unstable_by_dims_total(&[0, 1]);
+        assert_eq!(floats.map(|point| point.1), [2.0, -1.0, 0.0, 1.0]);
+
+        let mut soa: SoaVec<_, 3> = [[2, 1, 0], [1, 5, 1], [2, 0, 2], [1, 5, 3], [0, 9, 4]]
+            .into_iter()
+            .collect();
+        soa.sort_by_dims(&[0, 1]);
+        assert_eq!(soa.column(0), [0, 1, 1, 2, 2]);
+        assert_eq!(soa.column(1), [9, 5, 5, 0, 1]);
+        assert_eq!(soa.column(2), [4, 1, 3, 2, 0]);
+        soa.sort_unstable_by_dim(2);
+        assert_eq!(
+            soa.columns(),
+            [[2, 1, 2, 1, 0], [1, 5, 0, 5, 9], [0, 1, 2, 3, 4]]
+        );
+
+        let mut soa =
+            SoaVec::from_columns([vec![1.0, f64::NAN, -2.0], vec![0.0, 1.0, 2.0]]).unwrap();
+        soa.sort_by_dim_total(0);
+        assert_eq!(soa.column(1), [2.0, 0.0, 1.0]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "vector 1 has no item for dimension 2")]
+    fn sort_missing_component() {
+        let mut vectors = [vec![1, 2, 3], vec![4, 5]];
+        SortVectorArray::<_, 3, _>::sort_unstable_by_dim(&mut vectors[..], 2);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic(expected = "vector 1 has no item for dimension 2")]
+    fn iter_jagged() {
+        let vectors = vec![vec![1, 2, 3], vec![4, 5], vec![6, 7, 8]];
+        let [_, _, z] = VectorArray::<_, 3, _, _>::vec_split_safe(&vectors);
+        let mut iter = z.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(&8));
+        assert_eq!(iter.next(), Some(&3));
+        iter.next();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn swap_jagged() {
+        let mut vectors = vec![vec![1, 2], vec![3], vec![4, 5]];
+        let [_, mut y] = VectorArray::<_, 2, _, _>::vec_split_safe_mut(&mut vectors);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| y.swap(0, 1)));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(message, "vector 1 has no item for dimension 1");
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| y.rotate_left(1)));
+        assert!(result.is_err());
+        y.swap(0, 2);
+        assert_eq!(vectors, [vec![1, 5], vec![3], vec![4, 2]]);
+    }
 }
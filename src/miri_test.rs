@@ -0,0 +1,272 @@
+//! Tests for the aliasing rules of the accessors. These pass on their own, but
+//! are meant to be run with `cargo +nightly miri test`, which will catch any
+//! accessor invalidating the references of another one.
+
+use crate::SizedVectorArray;
+#[cfg(feature = "alloc")]
+use crate::{
+    accessors::{IterateAccessor, IterateAccessorMut},
+    dyn_array::{DynVectorArrayMut, FlatArray},
+    soa_vec::SoaVec,
+    Accessor, AccessorMut, VectorArray,
+};
+
+#[cfg(feature = "alloc")]
+#[test]
+fn fast_mut_interleaved() {
+    let mut vec = vec![[1, 2, 3], [4, 5, 6]];
+    let [mut x, mut y, mut z] = vec.vec_split_fast_mut();
+    let x0 = x.get_mut(0).unwrap();
+    let y0 = y.get_mut(0).unwrap();
+    let z1 = z.get_mut(1).unwrap();
+    *x0 += 10;
+    *y0 += 10;
+    *z1 += 10;
+    *x0 += 10;
+    assert_eq!(x.get(2), None);
+    assert_eq!(vec, vec![[21, 12, 3], [4, 5, 16]]);
+}
+
+#[test]
+fn fast_mut_iterators() {
+    let mut array = [(1, 2); 4];
+    let [mut x, mut y] = array.vec_split_fast_mut();
+    let xs = x.iter_mut().collect::<Vec<_>>();
+    let ys = y.iter_mut().rev().collect::<Vec<_>>();
+    for (x, y) in xs.into_iter().zip(ys) {
+        *x += 1;
+        *y *= 3;
+    }
+    assert_eq!(array, [(2, 6); 4]);
+
+    let mut array = [[0u8; 2]; 5];
+    let mut slice = &mut array[..];
+    let [mut x, mut y] = slice.vec_split_fast_mut();
+    let (mut a, mut b) = x.split_at_mut(2);
+    let a0 = a.get_mut(0).unwrap();
+    let b0 = b.get_mut(0).unwrap();
+    for item in y.iter_mut() {
+        *item = 1;
+    }
+    *a0 = 2;
+    *b0 = 3;
+    assert_eq!(array, [[2, 1], [0, 1], [3, 1], [0, 1], [0, 1]]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn fast_immutable() {
+    let vec = vec![(1u16, 2u16), (3, 4)];
+    let [x, y] = vec.vec_split_fast();
+    let x1 = x.get(1).unwrap();
+    assert_eq!(y.iter().copied().collect::<Vec<_>>(), vec![2, 4]);
+    assert_eq!(*x1, 3);
+    assert_eq!(x.get(2), None);
+    assert_eq!(Accessor::get(&y, 2), None);
+
+    let empty: Vec<[u64; 3]> = Vec::new();
+    let [x, _, z] = empty.vec_split_fast();
+    assert_eq!(x.iter().count(), 0);
+    assert_eq!(z.split_at(0).1.len(), 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn safe_mut_interleaved() {
+    let mut vec = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+    let [mut x, mut y] = vec.vec_split_safe_mut();
+    let x0 = x.get_mut(0).unwrap();
+    let y2 = y.get_mut(2).unwrap();
+    *x0 = 10;
+    *y2 = 60;
+    *x0 += 1;
+    y[0] = 20;
+    assert_eq!(x.get(3), None);
+    assert_eq!(vec, vec![vec![11, 20], vec![3, 4], vec![5, 60]]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn safe_mut_iterators() {
+    let mut vec = vec![(1, 2, 3); 3];
+    let [mut x, mut y, mut z] = vec.vec_split_safe_mut();
+    let xs = x.iter_mut().collect::<Vec<_>>();
+    let ys = y.iter_mut().collect::<Vec<_>>();
+    for ((x, y), z) in xs.into_iter().zip(ys).zip(z.iter_mut()) {
+        *x += 1;
+        *y += 1;
+        *z += 1;
+    }
+    assert_eq!(vec, vec![(2, 3, 4); 3]);
+
+    let mut array = [[1, 2]; 3];
+    let [mut x, mut y] = array.vec_split_safe_mut();
+    for (x, y) in x.iter_mut().zip(y.iter_mut()) {
+        std::mem::swap(x, y);
+    }
+    assert_eq!(array, [[2, 1]; 3]);
+
+    let mut array = [vec![1, 2], vec![3, 4]];
+    let mut slice = &mut array[..];
+    let [mut x, y] = slice.vec_split_safe_mut();
+    let y0 = y.get(0).unwrap();
+    for item in x.iter_mut() {
+        *item += *y0;
+    }
+    assert_eq!(*y0, 2);
+    assert_eq!(array, [vec![3, 2], vec![5, 4]]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn split_at_mut_interleaved() {
+    let mut vec = vec![vec![1, 2], vec![3, 4], vec![5, 6], vec![7, 8]];
+    let [mut x, _] = vec.vec_split_safe_mut();
+    let (mut first, mut second) = x.split_at_mut(2);
+    let a = first.get_mut(1).unwrap();
+    let b = second.get_mut(0).unwrap();
+    std::mem::swap(a, b);
+    *a += 1;
+    assert_eq!(vec, vec![vec![1, 2], vec![6, 4], vec![3, 6], vec![7, 8]]);
+
+    let mut vec = vec![[1, 2], [3, 4], [5, 6], [7, 8]];
+    let [_, mut y] = vec.vec_split_fast_mut();
+    let (mut first, second) = y.split_at_mut(2);
+    let mut rest = second.into_slice(1..);
+    let a = first.get_mut(0).unwrap();
+    let b = rest.get_mut(0).unwrap();
+    *a += *b;
+    *b = 0;
+    assert_eq!(vec, vec![[1, 10], [3, 4], [5, 6], [7, 0]]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn chunks_mut_interleaved() {
+    let mut vec = vec![vec![1, 2]; 5];
+    let [mut x, _] = vec.vec_split_safe_mut();
+    let mut chunks = x.chunks_mut(2).collect::<Vec<_>>();
+    let a = chunks[0].get_mut(1).unwrap() as *mut i32;
+    for chunk in chunks.iter_mut().rev() {
+        chunk[0] = 0;
+    }
+    // SAFETY: a points into the first chunk, which is still alive.
+    unsafe { *a = 7 };
+    drop(chunks);
+    assert_eq!(
+        vec,
+        vec![vec![0, 2], vec![7, 2], vec![0, 2], vec![1, 2], vec![0, 2]]
+    );
+
+    let mut vec = vec![[1, 2]; 5];
+    let [_, mut y] = vec.vec_split_fast_mut();
+    let chunks = y.chunks_exact_mut(2).collect::<Vec<_>>();
+    for (i, mut chunk) in chunks.into_iter().enumerate() {
+        chunk.iter_mut().for_each(|y| *y = i);
+    }
+    assert_eq!(
+        vec.iter().map(|v| v[1]).collect::<Vec<_>>(),
+        vec![0, 0, 1, 1, 2]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn mixed_interleaved() {
+    let mut vec = vec![vec![1, 2, 3], vec![4, 5, 6]];
+    let ([mut z], [x, y]) = VectorArray::<_, 3, _, _>::vec_split_safe_mixed(&mut vec, [2], [0, 1]);
+    let x0 = x.get(0).unwrap();
+    for (z, y) in z.iter_mut().zip(y.iter()) {
+        *z = *x0 * 10 + *y;
+    }
+    assert_eq!(*x0, 1);
+    assert_eq!(vec, vec![vec![1, 2, 12], vec![4, 5, 15]]);
+
+    let mut vec = vec![(1, 2, 3); 2];
+    let ([mut x], [y, z]) = vec.vec_split_fast_mixed([0], [1, 2]);
+    let y0 = y.first().unwrap();
+    for x in x.iter_mut() {
+        *x = *y0 * z[1];
+    }
+    assert_eq!(*y0, 2);
+    assert_eq!(vec, vec![(6, 2, 3); 2]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn dyn_interleaved() {
+    let mut rows = vec![vec![1, 2, 3], vec![4, 5, 6]];
+    let mut dims = rows.vec_split_dyn_mut(3).unwrap();
+    let [x, y, z] = &mut dims[..] else {
+        unreachable!()
+    };
+    let x0 = x.get_mut(0).unwrap();
+    for (y, z) in y.iter_mut().zip(z.iter_mut()) {
+        *y += *z;
+        *z = 0;
+    }
+    *x0 = 10;
+    drop(dims);
+    assert_eq!(rows, vec![vec![10, 5, 0], vec![4, 11, 0]]);
+
+    let mut flat = FlatArray::new(vec![1, 2, 3, 4], 2);
+    let mut dims = flat.vec_split_dyn_mut(2).unwrap();
+    let mut y = dims.pop().unwrap();
+    let mut x = dims.pop().unwrap();
+    let y1 = y.get_mut(1).unwrap();
+    for x in x.iter_mut() {
+        *x += *y1;
+        *y1 += 1;
+    }
+    assert_eq!(flat.into_inner(), vec![5, 2, 8, 6]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn safe_immutable() {
+    let vec = vec![[1, 2], [3, 4]];
+    let [x, y] = vec.vec_split_safe();
+    let x0 = x.get(0).unwrap();
+    assert_eq!(y.iter().copied().collect::<Vec<_>>(), vec![2, 4]);
+    assert_eq!(*x0, 1);
+    assert_eq!(x.get(2), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn soa_vec_interleaved() {
+    let mut soa = [[1, 2], [3, 4]].into_iter().collect::<SoaVec<_, 2>>();
+    let [mut x, mut y] = soa.vec_split_safe_mut();
+    let xs = x.iter_mut().collect::<Vec<_>>();
+    let y1 = y.get_mut(1).unwrap();
+    for x in xs {
+        *x += *y1;
+        *y1 += 1;
+    }
+    assert_eq!(soa.columns(), [&[5, 8][..], &[2, 6][..]]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn par_iter_producer_interleaved() {
+    use rayon::iter::{plumbing::Producer, IntoParallelIterator, IntoParallelRefIterator};
+
+    // Splits the parallel iterators by hand, like rayon would, but without the thread pool.
+    let mut vec = vec![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+    let mut slice = &mut vec[..];
+    let [mut x, mut y, z] = slice.vec_split_safe_mut();
+    let (x0, x1) = (&mut x).into_par_iter().split_at(1);
+    let (y0, y1) = (&mut y).into_par_iter().split_at(2);
+    let (z0, z1) = z.par_iter().split_at(1);
+    let x1_0 = Producer::into_iter(x1).next().unwrap();
+    let y0_1 = Producer::into_iter(y0).nth(1).unwrap();
+    let z0_0 = Producer::into_iter(z0).next().unwrap();
+    let x0_0 = Producer::into_iter(x0).next().unwrap();
+    *x1_0 += *z0_0;
+    *y0_1 += *z0_0;
+    *x0_0 += 1;
+    for (y, z) in Producer::into_iter(y1).zip(Producer::into_iter(z1).skip(1)) {
+        *y += *z;
+    }
+    assert_eq!(vec, [[2, 2, 3], [7, 8, 6], [7, 17, 9]]);
+}
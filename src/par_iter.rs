@@ -0,0 +1,367 @@
+//! Parallel iterators over split dimensions, using rayon. Only available with
+//! the `rayon` feature.
+//!
+//! The fast accessors can always be iterated in parallel. The safe accessors
+//! can only be iterated in parallel if the array is a [`ContiguousArray`], as
+//! other arrays (like Vecs) have to reference parts of the array that other
+//! threads may be using to get to an item.
+
+use core::ptr;
+
+use rayon::iter::{
+    plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+    IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+};
+
+use crate::{
+    strided_slice::{Iter, IterMut},
+    *,
+};
+
+/// An array of [`RawVector`]s that are stored next to each other, like a
+/// slice, so a dimension of it can be viewed as a [`StridedSlice`].
+///
+/// # Safety
+///
+/// `base_ptr` MUST return a pointer to the first vector without creating a
+/// reference to any part of the array, and the array MUST contain `len`
+/// vectors, each `size_of::<V>()` bytes after the previous one.
+pub unsafe trait ContiguousArray<T, const D: usize, V: RawVector<T, D>>:
+    VectorArray<T, D, V, usize>
+{
+    /// Gets a pointer to the first vector.
+    ///
+    /// # Safety
+    ///
+    /// `this` MUST point to a valid array.
+    unsafe fn base_ptr(this: *const Self) -> *const V;
+
+    /// Like [`Self::base_ptr`], but the pointer can be written through.
+    ///
+    /// # Safety
+    ///
+    /// `this` MUST point to a valid array, and be valid for writes.
+    unsafe fn base_ptr_mut(this: *mut Self) -> *mut V;
+}
+
+unsafe impl<T, const D: usize, V: RawVector<T, D>> ContiguousArray<T, D, V> for &mut [V] {
+    unsafe fn base_ptr(this: *const Self) -> *const V {
+        ptr::addr_of!(**this) as *const V
+    }
+
+    unsafe fn base_ptr_mut(this: *mut Self) -> *mut V {
+        // addr_of would make a pointer that may only be read through.
+        ptr::addr_of_mut!(**this) as *mut V
+    }
+}
+
+unsafe impl<T, const D: usize, V: RawVector<T, D>, const A: usize> ContiguousArray<T, D, V>
+    for [V; A]
+{
+    unsafe fn base_ptr(this: *const Self) -> *const V {
+        this as *const V
+    }
+
+    unsafe fn base_ptr_mut(this: *mut Self) -> *mut V {
+        this as *mut V
+    }
+}
+
+// SAFETY: Aliased has the same layout as the array it wraps.
+unsafe impl<T, const D: usize, V: RawVector<T, D>, VA: ContiguousArray<T, D, V>>
+    ContiguousArray<T, D, V> for Aliased<VA>
+{
+    unsafe fn base_ptr(this: *const Self) -> *const V {
+        VA::base_ptr(this as *const VA)
+    }
+
+    unsafe fn base_ptr_mut(this: *mut Self) -> *mut V {
+        VA::base_ptr_mut(this as *mut VA)
+    }
+}
+
+/// Gets the pointer to the first item of a dimension of a contiguous array.
+///
+/// # Safety
+///
+/// `this` MUST point to a valid array, and `start` MUST be at most its length.
+unsafe fn dim_ptr<T, const D: usize, V: RawVector<T, D>, VA: ContiguousArray<T, D, V>>(
+    this: *const VA,
+    dim: usize,
+    start: usize,
+) -> *const T {
+    VA::base_ptr(this)
+        .wrapping_add(start)
+        .cast::<T>()
+        .wrapping_byte_add(V::OFFSETS[dim])
+}
+
+/// Like [`dim_ptr`], but the pointer can be written through.
+///
+/// # Safety
+///
+/// See [`dim_ptr`]. `this` MUST also be valid for writes.
+unsafe fn dim_ptr_mut<T, const D: usize, V: RawVector<T, D>, VA: ContiguousArray<T, D, V>>(
+    this: *mut VA,
+    dim: usize,
+    start: usize,
+) -> *mut T {
+    VA::base_ptr_mut(this)
+        .wrapping_add(start)
+        .cast::<T>()
+        .wrapping_byte_add(V::OFFSETS[dim])
+}
+
+/// A parallel iterator over the items of an immutable accessor.
+pub struct ParIter<'a, T> {
+    slice: StridedSlice<'a, T>,
+}
+
+/// A parallel iterator over the items of a mutable accessor.
+pub struct ParIterMut<'a, T> {
+    slice: StridedSliceMut<'a, T>,
+}
+
+impl<'a, T: Sync> ParallelIterator for ParIter<'a, T> {
+    type Item = &'a T;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.slice.len())
+    }
+}
+
+impl<'a, T: Sync> IndexedParallelIterator for ParIter<'a, T> {
+    fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self)
+    }
+}
+
+impl<'a, T: Sync> Producer for ParIter<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.slice.iter()
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.slice.split_at(index);
+        (Self { slice: left }, Self { slice: right })
+    }
+}
+
+impl<'a, T: Send> ParallelIterator for ParIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.slice.len())
+    }
+}
+
+impl<'a, T: Send> IndexedParallelIterator for ParIterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self)
+    }
+}
+
+impl<'a, T: Send> Producer for ParIterMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.slice.into_iter()
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.slice.into_split_at(index);
+        (Self { slice: left }, Self { slice: right })
+    }
+}
+
+impl<'a, T: Sync> IntoParallelIterator for StridedSlice<'a, T> {
+    type Iter = ParIter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter { slice: self }
+    }
+}
+
+impl<'a, 'b, T: Sync> IntoParallelIterator for &'b StridedSlice<'a, T> {
+    type Iter = ParIter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter { slice: *self }
+    }
+}
+
+impl<'a, T: Send> IntoParallelIterator for StridedSliceMut<'a, T> {
+    type Iter = ParIterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIterMut { slice: self }
+    }
+}
+
+impl<'a, 'b, T: Sync> IntoParallelIterator for &'b StridedSliceMut<'a, T> {
+    type Iter = ParIter<'b, T>;
+    type Item = &'b T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter {
+            slice: self.as_strided_slice(),
+        }
+    }
+}
+
+impl<'a, 'b, T: Send> IntoParallelIterator for &'b mut StridedSliceMut<'a, T> {
+    type Iter = ParIterMut<'b, T>;
+    type Item = &'b mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIterMut {
+            slice: self.reborrow(),
+        }
+    }
+}
+
+impl<'a, T, const D: usize, V: RawVector<T, D>, VA: ContiguousArray<T, D, V>>
+    SafeAccessor<'a, T, D, V, usize, VA>
+{
+    /// Views the accessor as a [`StridedSlice`].
+    fn to_strided_slice(&self) -> StridedSlice<'a, T> {
+        let (array_ptr, dim, start, len) = self.raw_parts();
+        // SAFETY: the array is contiguous and outlives 'a, and start..start + len is within it.
+        unsafe {
+            StridedSlice::from_raw_parts(dim_ptr(array_ptr, dim, start), len, mem::size_of::<V>())
+        }
+    }
+}
+
+impl<'a, T, const D: usize, V: RawVector<T, D>, VA: ContiguousArray<T, D, V>>
+    SafeAccessorMut<'a, T, D, V, usize, VA>
+{
+    /// Views the accessor as a [`StridedSlice`].
+    fn as_strided_slice(&self) -> StridedSlice<'_, T> {
+        let (array_ptr, dim, start, len) = self.raw_parts();
+        // SAFETY: the array is contiguous and outlives self, and start..start + len is within
+        // it. Only this accessor can access these items, and it is borrowed immutably.
+        unsafe {
+            StridedSlice::from_raw_parts(dim_ptr(array_ptr, dim, start), len, mem::size_of::<V>())
+        }
+    }
+
+    /// Views the accessor as a [`StridedSliceMut`].
+    fn as_strided_slice_mut(&mut self) -> StridedSliceMut<'_, T> {
+        let (array_ptr, dim, start, len) = self.raw_parts();
+        // SAFETY: the array is contiguous and outlives self, and start..start + len is within
+        // it. Only this accessor can access these items, and it is borrowed mutably.
+        unsafe {
+            StridedSliceMut::from_raw_parts(
+                dim_ptr_mut(array_ptr, dim, start),
+                len,
+                mem::size_of::<V>(),
+            )
+        }
+    }
+
+    /// Turns the accessor into a [`StridedSliceMut`] with the same lifetime.
+    fn into_strided_slice_mut(mut self) -> StridedSliceMut<'a, T> {
+        let slice = self.as_strided_slice_mut();
+        // SAFETY: self is consumed, so the items can be borrowed for all of 'a.
+        unsafe {
+            StridedSliceMut::from_raw_parts(slice.as_ptr() as *mut T, slice.len(), slice.stride())
+        }
+    }
+}
+
+impl<'a, T: Sync + 'a, const D: usize, V: RawVector<T, D>, VA: ContiguousArray<T, D, V>>
+    IntoParallelIterator for SafeAccessor<'a, T, D, V, usize, VA>
+{
+    type Iter = ParIter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter {
+            slice: self.to_strided_slice(),
+        }
+    }
+}
+
+impl<'a, 'b, T: Sync + 'a, const D: usize, V: RawVector<T, D>, VA: ContiguousArray<T, D, V>>
+    IntoParallelIterator for &'b SafeAccessor<'a, T, D, V, usize, VA>
+{
+    type Iter = ParIter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter {
+            slice: self.to_strided_slice(),
+        }
+    }
+}
+
+impl<'a, T: Send + 'a, const D: usize, V: RawVector<T, D>, VA: ContiguousArray<T, D, V>>
+    IntoParallelIterator for SafeAccessorMut<'a, T, D, V, usize, VA>
+{
+    type Iter = ParIterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIterMut {
+            slice: self.into_strided_slice_mut(),
+        }
+    }
+}
+
+impl<'a, 'b, T: Sync + 'a, const D: usize, V: RawVector<T, D>, VA: ContiguousArray<T, D, V>>
+    IntoParallelIterator for &'b SafeAccessorMut<'a, T, D, V, usize, VA>
+{
+    type Iter = ParIter<'b, T>;
+    type Item = &'b T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter {
+            slice: self.as_strided_slice(),
+        }
+    }
+}
+
+impl<'a, 'b, T: Send + 'a, const D: usize, V: RawVector<T, D>, VA: ContiguousArray<T, D, V>>
+    IntoParallelIterator for &'b mut SafeAccessorMut<'a, T, D, V, usize, VA>
+{
+    type Iter = ParIterMut<'b, T>;
+    type Item = &'b mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIterMut {
+            slice: self.as_strided_slice_mut(),
+        }
+    }
+}
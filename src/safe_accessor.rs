@@ -1,13 +1,18 @@
-use std::{
+use core::{
     marker::PhantomData,
-    ops::{Index, IndexMut},
+    ops::{Index, IndexMut, RangeBounds},
 };
 
-use crate::*;
+use crate::{
+    chunks::{Chunks, ChunksExact, SplitAccessor},
+    error::SplitError,
+    *,
+};
 
 /// The immutable fast accessor. Contains a reference to the array, and the
 /// dimension to get from the vectors. Unlike the Fast accessor, this can be
-/// used for **ALL** types of vectors and arrays.
+/// used for **ALL** types of vectors and arrays. It is indexed by position,
+/// like a slice, whatever the index type `I` of the array is.
 pub struct SafeAccessor<
     'a,
     T: ?Sized,
@@ -19,45 +24,220 @@ pub struct SafeAccessor<
     phantom_t: PhantomData<T>,
     phantom_v: PhantomData<V>,
     phantom_i: PhantomData<I>,
-    array_ref: &'a VA,
+    phantom_va: PhantomData<&'a VA>,
+    array_ptr: *const VA,
     dim: usize,
+    start: usize,
+    len: usize,
+}
+
+// SAFETY: a SafeAccessor behaves like a &'a VA that only hands out &T. Accessors that were split
+// next to mutable ones have an Aliased array, which is neither Send nor Sync.
+unsafe impl<'a, T: ?Sized + Sync, const D: usize, V: Vector<T, D>, I, VA> Send
+    for SafeAccessor<'a, T, D, V, I, VA>
+where
+    VA: VectorArray<T, D, V, I> + Sync,
+{
+}
+// SAFETY: see Send.
+unsafe impl<'a, T: ?Sized + Sync, const D: usize, V: Vector<T, D>, I, VA> Sync
+    for SafeAccessor<'a, T, D, V, I, VA>
+where
+    VA: VectorArray<T, D, V, I> + Sync,
+{
 }
 
 impl<'a, T: ?Sized, const D: usize, V: Vector<T, D>, I, VA: VectorArray<T, D, V, I>>
     SafeAccessor<'a, T, D, V, I, VA>
 {
-    pub(crate) fn new(array_ref: &'a VA, dim: usize) -> Self {
+    pub(crate) fn new(array_ref: &'a VA, dim: usize, len: usize) -> Self {
+        // SAFETY: the array is borrowed immutably for 'a, so nothing can mutate it.
+        unsafe { Self::from_ptr(array_ref, dim, len) }
+    }
+
+    /// # Safety
+    ///
+    /// `array_ptr` MUST be valid for `'a`, `len` MUST be the length of the
+    /// array, and no mutable accessor for the same dimension may exist at the
+    /// same time.
+    pub(crate) unsafe fn from_ptr(array_ptr: *const VA, dim: usize, len: usize) -> Self {
         Self {
             phantom_t: PhantomData,
             phantom_v: PhantomData,
             phantom_i: PhantomData,
-            array_ref,
+            phantom_va: PhantomData,
+            array_ptr,
             dim,
+            start: 0,
+            len,
+        }
+    }
+
+    /// The pointer to the array, the dimension, and the range of vectors this
+    /// accessor covers.
+    #[cfg(any(feature = "rayon", test))]
+    pub(crate) fn raw_parts(&self) -> (*const VA, usize, usize, usize) {
+        (self.array_ptr, self.dim, self.start, self.len)
+    }
+
+    /// Returns an accessor over the vectors in `range` only. Index 0 of the
+    /// new accessor is index `range.start` of this one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = slice_range(range, self.len);
+        Self {
+            phantom_t: PhantomData,
+            phantom_v: PhantomData,
+            phantom_i: PhantomData,
+            phantom_va: PhantomData,
+            array_ptr: self.array_ptr,
+            dim: self.dim,
+            start: self.start + start,
+            len: end - start,
         }
     }
+
+    /// Divides the accessor into two at `mid`. The first will contain all
+    /// vectors from `[0, mid)`, the second all vectors from `[mid, len)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len, "mid is too large for array.");
+        (self.slice(..mid), self.slice(mid..))
+    }
 }
 
-impl<'a, T: ?Sized, const D: usize, V: Vector<T, D>, I, VA: VectorArray<T, D, V, I>> Index<I>
-    for SafeAccessor<'a, T, D, V, I, VA>
+impl<
+        'a,
+        T: ?Sized,
+        const D: usize,
+        V: Vector<T, D>,
+        I: TryFrom<usize>,
+        VA: VectorArray<T, D, V, I>,
+    > SafeAccessor<'a, T, D, V, I, VA>
+{
+    /// Returns an iterator over chunks of `size` vectors. The last chunk is
+    /// shorter if the length is not divisible by `size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> Chunks<T, Self> {
+        Chunks::new(self.slice(..), size)
+    }
+
+    /// Returns an iterator over chunks of exactly `size` vectors. The vectors
+    /// left over at the end are in [`ChunksExact::remainder`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks_exact(&self, size: usize) -> ChunksExact<T, Self> {
+        ChunksExact::new(self.slice(..), size)
+    }
+}
+
+impl<
+        'a,
+        T: ?Sized,
+        const D: usize,
+        V: Vector<T, D>,
+        I: TryFrom<usize>,
+        VA: VectorArray<T, D, V, I>,
+    > SplitAccessor<T> for SafeAccessor<'a, T, D, V, I, VA>
+{
+    fn into_split_at(self, mid: usize) -> (Self, Self) {
+        self.split_at(mid)
+    }
+}
+
+impl<
+        'a,
+        T: ?Sized,
+        const D: usize,
+        V: Vector<T, D>,
+        I: TryFrom<usize>,
+        VA: VectorArray<T, D, V, I>,
+    > Index<usize> for SafeAccessor<'a, T, D, V, I, VA>
 {
     type Output = T;
 
-    fn index(&self, index: I) -> &Self::Output {
-        self.get(index).expect("index is too large for array.")
+    fn index(&self, index: usize) -> &Self::Output {
+        self.try_get(index).unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
-impl<'a, T: ?Sized, const D: usize, V: Vector<T, D>, I, VA: VectorArray<T, D, V, I>> Accessor<T, I>
-    for SafeAccessor<'a, T, D, V, I, VA>
+impl<
+        'a,
+        T: ?Sized,
+        const D: usize,
+        V: Vector<T, D>,
+        I: TryFrom<usize>,
+        VA: VectorArray<T, D, V, I>,
+    > Accessor<T, usize> for SafeAccessor<'a, T, D, V, I, VA>
 {
-    fn get<'b>(&'b self, index: I) -> Option<&'b T> {
-        self.array_ref.get(index).map(|x| x.get(self.dim).unwrap())
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get<'b>(&'b self, index: usize) -> Option<&'b T> {
+        if index >= self.len {
+            return None;
+        }
+        let index = array_index(self.start + index)?;
+        // SAFETY: the array outlives self, and only this accessor's dimension is touched.
+        unsafe { VA::get_item_ptr(self.array_ptr, index, self.dim).map(|x| &*x) }
+    }
+    fn try_get<'b>(&'b self, index: usize) -> Result<&'b T, SplitError> {
+        if index >= self.len {
+            return Err(SplitError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+        self.get(index).ok_or(SplitError::MissingComponent {
+            index: self.start + index,
+            dim: self.dim,
+        })
+    }
+}
+
+/// The array of immutable [`SafeAccessor`]s that were split next to mutable
+/// ones of the same array, like by [`SafeAccessorMut::slice`]. It accesses the
+/// array the same way, but the mutable accessors may reference the whole array
+/// to get to their items, so these accessors cannot be sent to other threads.
+#[repr(transparent)]
+pub struct Aliased<VA> {
+    array: VA,
+    phantom: PhantomData<*const ()>,
+}
+
+// SAFETY: every method forwards to the array, which Aliased has the same layout as.
+unsafe impl<T: ?Sized, const D: usize, V: Vector<T, D>, I, VA: VectorArray<T, D, V, I>>
+    VectorArray<T, D, V, I> for Aliased<VA>
+{
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    unsafe fn get_item_ptr(this: *const Self, index: I, dim: usize) -> Option<*const T> {
+        VA::get_item_ptr(this as *const VA, index, dim)
+    }
+
+    unsafe fn get_item_ptr_mut(this: *mut Self, index: I, dim: usize) -> Option<*mut T> {
+        VA::get_item_ptr_mut(this as *mut VA, index, dim)
     }
 }
 
 /// The mutable fast accessor. Contains a mutable reference to the array, and
 /// the dimension to get from the vectors. Unlike the Fast accessor, this can
-/// be used for **ALL** types of vectors and arrays.
+/// be used for **ALL** types of vectors and arrays. Like [`SafeAccessor`], it
+/// is indexed by position.
 pub struct SafeAccessorMut<
     'a,
     T: ?Sized,
@@ -69,55 +249,325 @@ pub struct SafeAccessorMut<
     phantom_t: PhantomData<T>,
     phantom_v: PhantomData<V>,
     phantom_i: PhantomData<I>,
-    array_ref: &'a mut VA,
+    phantom_va: PhantomData<&'a mut VA>,
+    array_ptr: *mut VA,
     dim: usize,
+    start: usize,
+    len: usize,
 }
 
 impl<'a, T: ?Sized, const D: usize, V: Vector<T, D>, I, VA: VectorArray<T, D, V, I>>
     SafeAccessorMut<'a, T, D, V, I, VA>
 {
-    pub(crate) fn new(array_ref: &'a mut VA, dim: usize) -> Self {
+    /// # Safety
+    ///
+    /// `array_ptr` MUST be valid for `'a`, `len` MUST be the length of the
+    /// array, and no other accessor for the same dimension may exist at the
+    /// same time.
+    pub(crate) unsafe fn new(array_ptr: *mut VA, dim: usize, len: usize) -> Self {
         Self {
             phantom_t: PhantomData,
             phantom_v: PhantomData,
             phantom_i: PhantomData,
-            array_ref,
+            phantom_va: PhantomData,
+            array_ptr,
             dim,
+            start: 0,
+            len,
         }
     }
+
+    /// The pointer to the array, the dimension, and the range of vectors this
+    /// accessor covers.
+    #[cfg(any(feature = "rayon", test))]
+    pub(crate) fn raw_parts(&self) -> (*mut VA, usize, usize, usize) {
+        (self.array_ptr, self.dim, self.start, self.len)
+    }
+
+    /// Returns an immutable accessor over the vectors in `range` only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice(
+        &self,
+        range: impl RangeBounds<usize>,
+    ) -> SafeAccessor<'_, T, D, V, I, Aliased<VA>> {
+        let (start, end) = slice_range(range, self.len);
+        SafeAccessor {
+            phantom_t: PhantomData,
+            phantom_v: PhantomData,
+            phantom_i: PhantomData,
+            phantom_va: PhantomData,
+            array_ptr: self.array_ptr as *const Aliased<VA>,
+            dim: self.dim,
+            start: self.start + start,
+            len: end - start,
+        }
+    }
+
+    /// Returns a mutable accessor over the vectors in `range` only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice_mut(
+        &mut self,
+        range: impl RangeBounds<usize>,
+    ) -> SafeAccessorMut<'_, T, D, V, I, VA> {
+        self.reborrow().into_slice(range)
+    }
+
+    /// Like [`Self::slice_mut`], but consumes self so the accessor keeps its
+    /// lifetime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn into_slice(self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = slice_range(range, self.len);
+        Self {
+            start: self.start + start,
+            len: end - start,
+            ..self
+        }
+    }
+
+    /// Reborrows the accessor, so it can be used again after the returned one
+    /// is dropped.
+    pub fn reborrow(&mut self) -> SafeAccessorMut<'_, T, D, V, I, VA> {
+        SafeAccessorMut {
+            phantom_t: PhantomData,
+            phantom_v: PhantomData,
+            phantom_i: PhantomData,
+            phantom_va: PhantomData,
+            array_ptr: self.array_ptr,
+            dim: self.dim,
+            start: self.start,
+            len: self.len,
+        }
+    }
+
+    /// Divides the accessor into two immutable halves at `mid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at(
+        &self,
+        mid: usize,
+    ) -> (
+        SafeAccessor<'_, T, D, V, I, Aliased<VA>>,
+        SafeAccessor<'_, T, D, V, I, Aliased<VA>>,
+    ) {
+        assert!(mid <= self.len, "mid is too large for array.");
+        (self.slice(..mid), self.slice(mid..))
+    }
+
+    /// Divides the accessor into two mutable halves at `mid`. The first will
+    /// contain all vectors from `[0, mid)`, the second all vectors from
+    /// `[mid, len)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at_mut(
+        &mut self,
+        mid: usize,
+    ) -> (
+        SafeAccessorMut<'_, T, D, V, I, VA>,
+        SafeAccessorMut<'_, T, D, V, I, VA>,
+    ) {
+        self.reborrow().into_split_at(mid)
+    }
+
+    /// Like [`Self::split_at_mut`], but consumes self so the halves keep its
+    /// lifetime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn into_split_at(self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len, "mid is too large for array.");
+        let second = Self {
+            start: self.start + mid,
+            len: self.len - mid,
+            ..self
+        };
+        // The halves do not overlap, so they never hand out the same item.
+        let first = Self { len: mid, ..self };
+        (first, second)
+    }
+}
+
+impl<
+        'a,
+        T: ?Sized,
+        const D: usize,
+        V: Vector<T, D>,
+        I: TryFrom<usize>,
+        VA: VectorArray<T, D, V, I>,
+    > Accessor<T, usize> for SafeAccessorMut<'a, T, D, V, I, VA>
+{
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get<'b>(&'b self, index: usize) -> Option<&'b T> {
+        if index >= self.len {
+            return None;
+        }
+        let index = array_index(self.start + index)?;
+        // SAFETY: the array outlives self, and only this accessor's dimension is touched.
+        unsafe { VA::get_item_ptr(self.array_ptr, index, self.dim).map(|x| &*x) }
+    }
+    fn try_get<'b>(&'b self, index: usize) -> Result<&'b T, SplitError> {
+        if index >= self.len {
+            return Err(SplitError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+        self.get(index).ok_or(SplitError::MissingComponent {
+            index: self.start + index,
+            dim: self.dim,
+        })
+    }
+}
+impl<
+        'a,
+        T: ?Sized,
+        const D: usize,
+        V: Vector<T, D>,
+        I: TryFrom<usize>,
+        VA: VectorArray<T, D, V, I>,
+    > AccessorMut<T, usize> for SafeAccessorMut<'a, T, D, V, I, VA>
+{
+    fn get_mut<'b>(&'b mut self, index: usize) -> Option<&'b mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let index = array_index(self.start + index)?;
+        // SAFETY: the array outlives self, and only this accessor's dimension and range are
+        // touched, which no other accessor can do.
+        unsafe { VA::get_item_ptr_mut(self.array_ptr, index, self.dim).map(|x| &mut *x) }
+    }
+    fn try_get_mut<'b>(&'b mut self, index: usize) -> Result<&'b mut T, SplitError> {
+        if index >= self.len {
+            return Err(SplitError::IndexOutOfBounds {
+                index,
+                len: self.len,
+            });
+        }
+        let (start, dim) = (self.start, self.dim);
+        self.get_mut(index).ok_or(SplitError::MissingComponent {
+            index: start + index,
+            dim,
+        })
+    }
+}
+
+// SAFETY: VectorArray promises that every (index, dim) is its own item, and the accessor always
+// asks for the same one for an index.
+unsafe impl<'a, T, const D: usize, V: Vector<T, D>, I: TryFrom<usize>, VA: VectorArray<T, D, V, I>>
+    DisjointAccessorMut<T> for SafeAccessorMut<'a, T, D, V, I, VA>
+{
 }
 
-impl<'a, T: ?Sized, const D: usize, V: Vector<T, D>, I, VA: VectorArray<T, D, V, I>> Accessor<T, I>
-    for SafeAccessorMut<'a, T, D, V, I, VA>
+impl<
+        'a,
+        T: ?Sized,
+        const D: usize,
+        V: Vector<T, D>,
+        I: TryFrom<usize>,
+        VA: VectorArray<T, D, V, I>,
+    > SafeAccessorMut<'a, T, D, V, I, VA>
 {
-    fn get<'b>(&'b self, index: I) -> Option<&'b T> {
-        self.array_ref.get(index).map(|x| x.get(self.dim).unwrap())
+    /// Returns an iterator over immutable chunks of `size` vectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> Chunks<T, SafeAccessor<'_, T, D, V, I, Aliased<VA>>> {
+        Chunks::new(self.slice(..), size)
+    }
+
+    /// Returns an iterator over immutable chunks of exactly `size` vectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks_exact(
+        &self,
+        size: usize,
+    ) -> ChunksExact<T, SafeAccessor<'_, T, D, V, I, Aliased<VA>>> {
+        ChunksExact::new(self.slice(..), size)
+    }
+
+    /// Returns an iterator over mutable chunks of `size` vectors. The chunks
+    /// do not overlap, so they can be used at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks_mut(&mut self, size: usize) -> Chunks<T, SafeAccessorMut<'_, T, D, V, I, VA>> {
+        Chunks::new(self.reborrow(), size)
+    }
+
+    /// Returns an iterator over mutable chunks of exactly `size` vectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks_exact_mut(
+        &mut self,
+        size: usize,
+    ) -> ChunksExact<T, SafeAccessorMut<'_, T, D, V, I, VA>> {
+        ChunksExact::new(self.reborrow(), size)
     }
 }
-impl<'a, T: ?Sized, const D: usize, V: Vector<T, D>, I, VA: VectorArray<T, D, V, I>>
-    AccessorMut<T, I> for SafeAccessorMut<'a, T, D, V, I, VA>
+
+impl<
+        'a,
+        T: ?Sized,
+        const D: usize,
+        V: Vector<T, D>,
+        I: TryFrom<usize>,
+        VA: VectorArray<T, D, V, I>,
+    > SplitAccessor<T> for SafeAccessorMut<'a, T, D, V, I, VA>
 {
-    fn get_mut<'b>(&'b mut self, index: I) -> Option<&'b mut T> {
-        self.array_ref
-            .get_mut(index)
-            .map(|x| x.get_mut(self.dim).unwrap())
+    fn into_split_at(self, mid: usize) -> (Self, Self) {
+        SafeAccessorMut::into_split_at(self, mid)
     }
 }
 
-impl<'a, T: ?Sized, const D: usize, V: Vector<T, D>, I, VA: VectorArray<T, D, V, I>> Index<I>
-    for SafeAccessorMut<'a, T, D, V, I, VA>
+impl<
+        'a,
+        T: ?Sized,
+        const D: usize,
+        V: Vector<T, D>,
+        I: TryFrom<usize>,
+        VA: VectorArray<T, D, V, I>,
+    > Index<usize> for SafeAccessorMut<'a, T, D, V, I, VA>
 {
     type Output = T;
 
-    fn index(&self, index: I) -> &Self::Output {
-        self.get(index).expect("index is too large for array.")
+    fn index(&self, index: usize) -> &Self::Output {
+        self.try_get(index).unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
-impl<'a, T: ?Sized, const D: usize, V: Vector<T, D>, I, VA: VectorArray<T, D, V, I>> IndexMut<I>
-    for SafeAccessorMut<'a, T, D, V, I, VA>
+impl<
+        'a,
+        T: ?Sized,
+        const D: usize,
+        V: Vector<T, D>,
+        I: TryFrom<usize>,
+        VA: VectorArray<T, D, V, I>,
+    > IndexMut<usize> for SafeAccessorMut<'a, T, D, V, I, VA>
 {
-    fn index_mut(&mut self, index: I) -> &mut Self::Output {
-        self.get_mut(index).expect("index is too large for array.")
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.try_get_mut(index)
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 }
@@ -0,0 +1,206 @@
+//! An owned array of vectors that stores each dimension in its own Vec, also
+//! called a structure of arrays. It implements [`VectorArray`], so code
+//! written against it works the same on SoaVecs as it does on `Vec<[T; D]>`.
+
+use alloc::vec::Vec;
+use core::ptr;
+
+use crate::{columns::IntoColumns, error::SplitError, *};
+
+/// A structure-of-arrays Vec. Contains one Vec per dimension, all of which
+/// have the same length.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SoaVec<T, const D: usize> {
+    columns: [Vec<T>; D],
+}
+
+impl<T, const D: usize> SoaVec<T, D> {
+    pub fn new() -> Self {
+        Self {
+            columns: [(); D].map(|_| Vec::new()),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            columns: [(); D].map(|_| Vec::with_capacity(capacity)),
+        }
+    }
+
+    /// Creates a SoaVec from one Vec per dimension. All of them must have the
+    /// same length.
+    pub fn from_columns(columns: [Vec<T>; D]) -> Result<Self, SplitError> {
+        let expected = columns.first().map_or(0, Vec::len);
+        for (dim, column) in columns.iter().enumerate() {
+            if column.len() != expected {
+                return Err(SplitError::LengthMismatch {
+                    dim,
+                    expected,
+                    found: column.len(),
+                });
+            }
+        }
+        Ok(Self { columns })
+    }
+
+    /// The amount of vectors.
+    pub fn len(&self) -> usize {
+        self.columns.first().map_or(0, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends a vector to the end.
+    pub fn push(&mut self, vector: [T; D]) {
+        for (column, item) in self.columns.iter_mut().zip(vector) {
+            column.push(item);
+        }
+    }
+
+    /// Removes the last vector and returns it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<[T; D]> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.columns.each_mut().map(|column| column.pop().unwrap()))
+    }
+
+    /// Inserts a vector at `index`, moving all vectors after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, vector: [T; D]) {
+        assert!(index <= self.len(), "index is too large for array.");
+        for (column, item) in self.columns.iter_mut().zip(vector) {
+            column.insert(index, item);
+        }
+    }
+
+    /// Removes the vector at `index` and returns it, moving all vectors after
+    /// it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> [T; D] {
+        assert!(index < self.len(), "index is too large for array.");
+        self.columns.each_mut().map(|column| column.remove(index))
+    }
+
+    /// Shortens the SoaVec to `len` vectors. Does nothing if it is already
+    /// shorter.
+    pub fn truncate(&mut self, len: usize) {
+        for column in &mut self.columns {
+            column.truncate(len);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Gets the items of the vector at `index`.
+    pub fn get(&self, index: usize) -> Option<[&T; D]> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(self.columns.each_ref().map(|column| &column[index]))
+    }
+
+    /// Gets the items of the vector at `index` mutably.
+    pub fn get_mut(&mut self, index: usize) -> Option<[&mut T; D]> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(self.columns.each_mut().map(|column| &mut column[index]))
+    }
+
+    /// Gets all items of one dimension.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dim >= D`.
+    pub fn column(&self, dim: usize) -> &[T] {
+        &self.columns[dim]
+    }
+
+    /// Gets all items of one dimension mutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dim >= D`.
+    pub fn column_mut(&mut self, dim: usize) -> &mut [T] {
+        &mut self.columns[dim]
+    }
+
+    /// Gets all items of every dimension.
+    pub fn columns(&self) -> [&[T]; D] {
+        self.columns.each_ref().map(Vec::as_slice)
+    }
+
+    /// Gets all items of every dimension mutably.
+    pub fn columns_mut(&mut self) -> [&mut [T]; D] {
+        self.columns.each_mut().map(Vec::as_mut_slice)
+    }
+}
+
+impl<T, const D: usize> Default for SoaVec<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const D: usize> Extend<[T; D]> for SoaVec<T, D> {
+    fn extend<It: IntoIterator<Item = [T; D]>>(&mut self, iter: It) {
+        let iter = iter.into_iter();
+        let (additional, _) = iter.size_hint();
+        for column in &mut self.columns {
+            column.reserve(additional);
+        }
+        for vector in iter {
+            self.push(vector);
+        }
+    }
+}
+
+impl<T, const D: usize> FromIterator<[T; D]> for SoaVec<T, D> {
+    fn from_iter<It: IntoIterator<Item = [T; D]>>(iter: It) -> Self {
+        let mut soa_vec = Self::new();
+        soa_vec.extend(iter);
+        soa_vec
+    }
+}
+
+impl<T, const D: usize> IntoColumns<T, D> for SoaVec<T, D> {
+    fn into_columns(self) -> [Vec<T>; D] {
+        self.columns
+    }
+}
+
+// SAFETY: every dimension is its own Vec, and the columns all have len items.
+unsafe impl<T, const D: usize> VectorArray<T, D, [T; D], usize> for SoaVec<T, D> {
+    fn len(&self) -> usize {
+        SoaVec::len(self)
+    }
+
+    unsafe fn get_item_ptr(this: *const Self, index: usize, dim: usize) -> Option<*const T> {
+        if dim >= D {
+            return None;
+        }
+        // Only the Vec of this dimension is referenced, the other ones are left alone.
+        let column = &*(ptr::addr_of!((*this).columns) as *const Vec<T>).add(dim);
+        (index < column.len()).then(|| column.as_ptr().add(index))
+    }
+
+    unsafe fn get_item_ptr_mut(this: *mut Self, index: usize, dim: usize) -> Option<*mut T> {
+        if dim >= D {
+            return None;
+        }
+        // Only the Vec of this dimension is referenced, the other ones are left alone.
+        let column = &mut *(ptr::addr_of_mut!((*this).columns) as *mut Vec<T>).add(dim);
+        (index < column.len()).then(|| column.as_mut_ptr().add(index))
+    }
+}
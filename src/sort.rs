@@ -0,0 +1,236 @@
+//! Sorting of arrays of vectors by their dimensions. Works on anything that
+//! derefs to a slice of vectors, like Vecs, arrays and `&mut [V]`, and on
+//! `SoaVec`s.
+//!
+//! The stable sorts need the `alloc` feature, like `slice::sort_by`.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+#[cfg(feature = "alloc")]
+use crate::soa_vec::SoaVec;
+use crate::{stats::Float, *};
+
+/// Trait used to sort arrays of vectors by one or more dimensions. The
+/// `_total` variants order floats with [`f64::total_cmp`], so NaNs are sorted
+/// to the ends instead of scrambling the order.
+pub trait SortVectorArray<T, const D: usize, V: Vector<T, D>> {
+    /// Sorts the vectors by dimension `dim`, keeping the order of equal ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dim` is not less than `D`, or if a vector has no item for
+    /// it.
+    #[cfg(feature = "alloc")]
+    fn sort_by_dim(&mut self, dim: usize)
+    where
+        T: Ord,
+    {
+        self.sort_by_dims(&[dim]);
+    }
+
+    /// Like `sort_by_dim`, but may reorder equal vectors. Slices are
+    /// sorted without allocating, so this works without the `alloc` feature.
+    fn sort_unstable_by_dim(&mut self, dim: usize)
+    where
+        T: Ord,
+    {
+        self.sort_unstable_by_dims(&[dim]);
+    }
+
+    /// Sorts the vectors by the first of `dims`, then by the second where the
+    /// first is equal, and so on. Keeps the order of equal vectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dimension is not less than `D`, or if a vector has no item
+    /// for one.
+    #[cfg(feature = "alloc")]
+    fn sort_by_dims(&mut self, dims: &[usize])
+    where
+        T: Ord;
+
+    /// Like `sort_by_dims`, but may reorder equal vectors. Slices are
+    /// sorted without allocating, so this works without the `alloc` feature.
+    fn sort_unstable_by_dims(&mut self, dims: &[usize])
+    where
+        T: Ord;
+
+    /// Like [`Self::sort_by_dim`], but for floats.
+    #[cfg(feature = "alloc")]
+    fn sort_by_dim_total(&mut self, dim: usize)
+    where
+        T: Float,
+    {
+        self.sort_by_dims_total(&[dim]);
+    }
+
+    /// Like [`Self::sort_unstable_by_dim`], but for floats.
+    fn sort_unstable_by_dim_total(&mut self, dim: usize)
+    where
+        T: Float,
+    {
+        self.sort_unstable_by_dims_total(&[dim]);
+    }
+
+    /// Like [`Self::sort_by_dims`], but for floats.
+    #[cfg(feature = "alloc")]
+    fn sort_by_dims_total(&mut self, dims: &[usize])
+    where
+        T: Float;
+
+    /// Like [`Self::sort_unstable_by_dims`], but for floats.
+    fn sort_unstable_by_dims_total(&mut self, dims: &[usize])
+    where
+        T: Float;
+}
+
+impl<T, const D: usize, V: Vector<T, D>> SortVectorArray<T, D, V> for [V] {
+    #[cfg(feature = "alloc")]
+    fn sort_by_dims(&mut self, dims: &[usize])
+    where
+        T: Ord,
+    {
+        check_items(self, dims);
+        self.sort_by(|a, b| compare_dims(a, b, dims, T::cmp));
+    }
+
+    fn sort_unstable_by_dims(&mut self, dims: &[usize])
+    where
+        T: Ord,
+    {
+        check_items(self, dims);
+        self.sort_unstable_by(|a, b| compare_dims(a, b, dims, T::cmp));
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sort_by_dims_total(&mut self, dims: &[usize])
+    where
+        T: Float,
+    {
+        check_items(self, dims);
+        self.sort_by(|a, b| compare_dims(a, b, dims, T::total_cmp));
+    }
+
+    fn sort_unstable_by_dims_total(&mut self, dims: &[usize])
+    where
+        T: Float,
+    {
+        check_items(self, dims);
+        self.sort_unstable_by(|a, b| compare_dims(a, b, dims, T::total_cmp));
+    }
+}
+
+/// Sorts all columns together, by sorting the indices of the vectors first and
+/// then moving every column into that order.
+#[cfg(feature = "alloc")]
+impl<T, const D: usize> SortVectorArray<T, D, [T; D]> for SoaVec<T, D> {
+    fn sort_by_dims(&mut self, dims: &[usize])
+    where
+        T: Ord,
+    {
+        let mut order = soa_order(self, dims);
+        order.sort_by(|&a, &b| compare_rows(self, a, b, dims, T::cmp));
+        permute(self, &order);
+    }
+
+    fn sort_unstable_by_dims(&mut self, dims: &[usize])
+    where
+        T: Ord,
+    {
+        let mut order = soa_order(self, dims);
+        order.sort_unstable_by(|&a, &b| compare_rows(self, a, b, dims, T::cmp));
+        permute(self, &order);
+    }
+
+    fn sort_by_dims_total(&mut self, dims: &[usize])
+    where
+        T: Float,
+    {
+        let mut order = soa_order(self, dims);
+        order.sort_by(|&a, &b| compare_rows(self, a, b, dims, T::total_cmp));
+        permute(self, &order);
+    }
+
+    fn sort_unstable_by_dims_total(&mut self, dims: &[usize])
+    where
+        T: Float,
+    {
+        let mut order = soa_order(self, dims);
+        order.sort_unstable_by(|&a, &b| compare_rows(self, a, b, dims, T::total_cmp));
+        permute(self, &order);
+    }
+}
+
+/// Checks `dims` and gets the indices of the vectors of a SoaVec, in their
+/// current order. A SoaVec cannot miss items, so only the dimensions are
+/// checked.
+#[cfg(feature = "alloc")]
+fn soa_order<T, const D: usize>(soa: &SoaVec<T, D>, dims: &[usize]) -> Vec<usize> {
+    for &dim in dims {
+        assert!(dim < D, "dimension is too large for vector.");
+    }
+    (0..soa.len()).collect()
+}
+
+/// Compares the vectors at `a` and `b` of a SoaVec by `dims`, in order.
+#[cfg(feature = "alloc")]
+fn compare_rows<T, const D: usize>(
+    soa: &SoaVec<T, D>,
+    a: usize,
+    b: usize,
+    dims: &[usize],
+    compare: impl Fn(&T, &T) -> Ordering,
+) -> Ordering {
+    dims.iter()
+        .map(|&dim| compare(&soa.column(dim)[a], &soa.column(dim)[b]))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Moves the vector at `order[i]` to `i` in every column, by following the
+/// cycles of the permutation, so that nothing has to be cloned.
+#[cfg(feature = "alloc")]
+fn permute<T, const D: usize>(soa: &mut SoaVec<T, D>, order: &[usize]) {
+    let mut done = alloc::vec![false; order.len()];
+    let mut columns = soa.columns_mut();
+    for start in 0..order.len() {
+        let mut i = start;
+        while !done[i] {
+            done[i] = true;
+            let next = order[i];
+            if next == start {
+                break;
+            }
+            for column in &mut columns {
+                column.swap(i, next);
+            }
+            i = next;
+        }
+    }
+}
+
+/// Checks that every vector has an item for every dimension in `dims`, so
+/// that the comparisons cannot fail halfway through a sort.
+fn check_items<T, const D: usize, V: Vector<T, D>>(vectors: &[V], dims: &[usize]) {
+    for &dim in dims {
+        assert!(dim < D, "dimension is too large for vector.");
+        if let Some(index) = vectors.iter().position(|vector| vector.get(dim).is_none()) {
+            panic!("{}", SplitError::MissingComponent { index, dim });
+        }
+    }
+}
+
+/// Compares two vectors by `dims`, in order.
+fn compare_dims<T, const D: usize, V: Vector<T, D>>(
+    a: &V,
+    b: &V,
+    dims: &[usize],
+    compare: impl Fn(&T, &T) -> Ordering,
+) -> Ordering {
+    dims.iter()
+        .map(|&dim| compare(a.get(dim).unwrap(), b.get(dim).unwrap()))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
@@ -0,0 +1,354 @@
+//! Reductions and statistics over split dimensions, like sums, extrema and
+//! means. They work on any [`Accessor`] whose items are a [`Number`].
+
+use core::ops::{Add, Div, Mul, Sub};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::cmp::Ordering;
+
+use crate::*;
+
+/// The primitive number types that the statistics can be computed for.
+pub trait Number:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Converts an amount of items into a number, rounding if needed.
+    fn from_usize(n: usize) -> Self;
+
+    /// Converts the number into an `f64`, rounding if needed.
+    fn to_f64(self) -> f64;
+
+    /// Converts an `f64` into this type, rounding towards zero and
+    /// saturating if needed.
+    fn from_f64(x: f64) -> Self;
+}
+
+macro_rules! impl_number {
+    ($zero:literal, $one:literal, $($type:ty),*) => {
+        $(
+            impl Number for $type {
+                const ZERO: Self = $zero;
+                const ONE: Self = $one;
+
+                fn from_usize(n: usize) -> Self {
+                    n as $type
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn from_f64(x: f64) -> Self {
+                    x as $type
+                }
+            }
+        )*
+    };
+}
+
+impl_number!(0, 1, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_number!(0.0, 1.0, f32, f64);
+
+/// The floating point number types, which statistics like the correlation
+/// need.
+pub trait Float: Number {
+    fn sqrt(self) -> Self;
+
+    /// Orders two floats like [`f64::total_cmp`], so that even NaNs can be
+    /// sorted.
+    fn total_cmp(&self, other: &Self) -> core::cmp::Ordering;
+}
+
+// core has no sqrt, so libm is used to stay no_std. Its arch feature is off, as that uses
+// inline assembly, which Miri cannot run.
+impl Float for f32 {
+    fn sqrt(self) -> Self {
+        libm::sqrtf(self)
+    }
+
+    fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+        f32::total_cmp(self, other)
+    }
+}
+
+impl Float for f64 {
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+
+    fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+        f64::total_cmp(self, other)
+    }
+}
+
+/// Whether `x` cannot be compared to anything, like NaN.
+fn is_nan<T: PartialOrd>(x: &T) -> bool {
+    x.partial_cmp(x).is_none()
+}
+
+/// Whether `item` should replace `best` when looking for the minimum. NaNs
+/// are only kept if there is nothing else.
+pub(crate) fn is_less<T: PartialOrd>(item: &T, best: &T) -> bool {
+    item < best || (is_nan(best) && !is_nan(item))
+}
+
+/// Whether `item` should replace `best` when looking for the maximum.
+pub(crate) fn is_greater<T: PartialOrd>(item: &T, best: &T) -> bool {
+    item > best || (is_nan(best) && !is_nan(item))
+}
+
+/// Gets the index of the best item of an accessor.
+fn arg_best<T: Number, A: Accessor<T, usize> + ?Sized>(
+    accessor: &A,
+    better: fn(&T, &T) -> bool,
+) -> Option<usize> {
+    if accessor.is_empty() {
+        return None;
+    }
+    let mut best = 0;
+    for i in 1..accessor.len() {
+        if better(&accessor[i], &accessor[best]) {
+            best = i;
+        }
+    }
+    Some(best)
+}
+
+/// Trait used to add reductions to structs implementing [`Accessor`]
+pub trait ReduceAccessor<T: Number>: Accessor<T, usize> {
+    /// Adds up all items. Returns 0 if the accessor is empty.
+    fn sum(&self) -> T {
+        (0..self.len()).fold(T::ZERO, |sum, i| sum + self[i])
+    }
+
+    /// Multiplies all items. Returns 1 if the accessor is empty.
+    fn product(&self) -> T {
+        (0..self.len()).fold(T::ONE, |product, i| product * self[i])
+    }
+
+    /// Gets the smallest item, or `None` if the accessor is empty. NaNs are
+    /// ignored, unless every item is NaN.
+    fn min(&self) -> Option<T> {
+        self.argmin().map(|i| self[i])
+    }
+
+    /// Gets the largest item, or `None` if the accessor is empty. NaNs are
+    /// ignored, unless every item is NaN.
+    fn max(&self) -> Option<T> {
+        self.argmax().map(|i| self[i])
+    }
+
+    /// Gets the index of the first smallest item, like [`Self::min`].
+    fn argmin(&self) -> Option<usize> {
+        arg_best(self, is_less)
+    }
+
+    /// Gets the index of the first largest item, like [`Self::max`].
+    fn argmax(&self) -> Option<usize> {
+        arg_best(self, is_greater)
+    }
+
+    /// Gets the average of the items, or `None` if the accessor is empty.
+    /// For integers, the result is rounded like integer division.
+    fn mean(&self) -> Option<T> {
+        (!self.is_empty()).then(|| self.sum() / T::from_usize(self.len()))
+    }
+
+    /// Gets the population variance of the items, or `None` if the accessor
+    /// is empty. For integers, the result is rounded like integer division.
+    fn variance(&self) -> Option<T> {
+        let mean = self.mean()?;
+        let squares = (0..self.len()).fold(T::ZERO, |sum, i| {
+            // Subtracting the other way around would underflow for unsigned integers.
+            let diff = if self[i] > mean {
+                self[i] - mean
+            } else {
+                mean - self[i]
+            };
+            sum + diff * diff
+        });
+        Some(squares / T::from_usize(self.len()))
+    }
+}
+
+impl<T: Number, A: Accessor<T, usize> + ?Sized> ReduceAccessor<T> for A {}
+
+/// Trait used to add order statistics to structs implementing [`Accessor`].
+/// The items are copied into a scratch Vec, so the array is never modified.
+/// NaNs are ignored.
+#[cfg(feature = "alloc")]
+pub trait QuantileAccessor<T: Number>: Accessor<T, usize> {
+    /// Gets the `q`-quantile of the items, interpolating linearly between the
+    /// two closest ones. Returns `None` if there are no items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is not between 0 and 1.
+    fn quantile(&self, q: f64) -> Option<T>
+    where
+        T: Float,
+    {
+        assert!((0.0..=1.0).contains(&q), "q must be between 0 and 1.");
+        let mut items = scratch_copy(self);
+        if items.is_empty() {
+            return None;
+        }
+        let position = q * (items.len() - 1) as f64;
+        let lower = position as usize;
+        let fraction = position - lower as f64;
+        let (_, &mut low, rest) = items.select_nth_unstable_by(lower, compare);
+        if fraction == 0.0 {
+            return Some(low);
+        }
+        // Everything after the selected item is at least as large, so the next one is the
+        // smallest of them.
+        let high = rest
+            .iter()
+            .copied()
+            .reduce(|a, b| if b < a { b } else { a })?;
+        Some(low + (high - low) * T::from_f64(fraction))
+    }
+
+    /// Gets the median of the items, like [`Self::quantile`] with 0.5.
+    fn median(&self) -> Option<T>
+    where
+        T: Float,
+    {
+        self.quantile(0.5)
+    }
+
+    /// Gets the `k` largest items, from largest to smallest. Returns fewer
+    /// if there are not enough items.
+    fn top_k(&self, k: usize) -> Vec<T> {
+        let mut items = scratch_copy(self);
+        if k < items.len() {
+            items.select_nth_unstable_by(k, |a, b| compare(b, a));
+            items.truncate(k);
+        }
+        items.sort_unstable_by(|a, b| compare(b, a));
+        items
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Number, A: Accessor<T, usize> + ?Sized> QuantileAccessor<T> for A {}
+
+/// Copies all items that are not NaN.
+#[cfg(feature = "alloc")]
+fn scratch_copy<T: Number, A: Accessor<T, usize> + ?Sized>(accessor: &A) -> Vec<T> {
+    (0..accessor.len())
+        .map(|i| accessor[i])
+        .filter(|item| !is_nan(item))
+        .collect()
+}
+
+/// Compares two items that are not NaN.
+#[cfg(feature = "alloc")]
+fn compare<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+/// Gets an item of an array, panicking if it is missing.
+pub(crate) fn expect_item<'a, T, const D: usize, V, I, VA>(
+    array: &'a VA,
+    index: usize,
+    dim: usize,
+) -> &'a T
+where
+    V: Vector<T, D>,
+    I: TryFrom<usize>,
+    VA: VectorArray<T, D, V, I>,
+{
+    array_index(index)
+        .and_then(|i| array.get_item(i, dim))
+        .unwrap_or_else(|| panic!("{}", SplitError::MissingComponent { index, dim }))
+}
+
+/// Gets the best item of every dimension in one pass over the array.
+pub(crate) fn per_dim_best<T: Number, const D: usize, V, I, VA>(
+    array: &VA,
+    better: fn(&T, &T) -> bool,
+) -> Option<[T; D]>
+where
+    V: Vector<T, D>,
+    I: TryFrom<usize>,
+    VA: VectorArray<T, D, V, I>,
+{
+    if array.is_empty() {
+        return None;
+    }
+    let mut best: [T; D] = core::array::from_fn(|dim| *expect_item(array, 0, dim));
+    for index in 1..array.len() {
+        for (dim, best) in best.iter_mut().enumerate() {
+            let item = expect_item(array, index, dim);
+            if better(item, best) {
+                *best = *item;
+            }
+        }
+    }
+    Some(best)
+}
+
+/// Sums up the products of the deviations from the mean of every pair of
+/// dimensions with Welford's algorithm, so that large offsets do not cancel
+/// out the precision.
+fn comoments<T: Float, A: Accessor<T, usize>, const D: usize>(
+    dims: &[A; D],
+) -> (usize, [[T; D]; D]) {
+    let len = dims.first().map_or(0, |dim| dim.len());
+    assert!(
+        dims.iter().all(|dim| dim.len() == len),
+        "lengths of the arrays do not match."
+    );
+    let mut means = [T::ZERO; D];
+    let mut comoments = [[T::ZERO; D]; D];
+    for index in 0..len {
+        let n = T::from_usize(index + 1);
+        let deltas: [T; D] = core::array::from_fn(|dim| dims[dim][index] - means[dim]);
+        for (mean, delta) in means.iter_mut().zip(deltas) {
+            *mean = *mean + delta / n;
+        }
+        for (row, delta) in comoments.iter_mut().zip(deltas) {
+            for (comoment, (dim, mean)) in row.iter_mut().zip(dims.iter().zip(means)) {
+                *comoment = *comoment + delta * (dim[index] - mean);
+            }
+        }
+    }
+    (len, comoments)
+}
+
+/// Gets the population covariance of every pair of dimensions in one pass.
+/// The diagonal holds the variances. Every item is NaN if the dimensions are
+/// empty.
+///
+/// # Panics
+///
+/// Panics if the dimensions do not have the same length.
+pub fn covariance<T: Float, A: Accessor<T, usize>, const D: usize>(dims: &[A; D]) -> [[T; D]; D] {
+    let (len, comoments) = comoments(dims);
+    let n = T::from_usize(len);
+    comoments.map(|row| row.map(|comoment| comoment / n))
+}
+
+/// Gets the Pearson correlation of every pair of dimensions in one pass. It
+/// is NaN for dimensions whose items are all the same.
+///
+/// # Panics
+///
+/// Panics if the dimensions do not have the same length.
+pub fn correlation<T: Float, A: Accessor<T, usize>, const D: usize>(dims: &[A; D]) -> [[T; D]; D] {
+    let (_, comoments) = comoments(dims);
+    core::array::from_fn(|i| {
+        core::array::from_fn(|j| comoments[i][j] / (comoments[i][i] * comoments[j][j]).sqrt())
+    })
+}
@@ -0,0 +1,831 @@
+//! Strided slices are views into memory where the items are not next to each
+//! other, but a constant amount of bytes apart. Splitting an array of vectors
+//! with [`SizedVectorArray::vec_split_fast`] returns one of these for each
+//! dimension.
+
+use core::{
+    fmt,
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::{Index, IndexMut, RangeBounds},
+    ptr,
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{
+    chunks::{Chunks, ChunksExact, SplitAccessor},
+    *,
+};
+
+/// An immutable strided slice. Contains a pointer to the first item, the
+/// amount of items, and the distance between two items in bytes.
+pub struct StridedSlice<'a, T> {
+    phantom_t: PhantomData<&'a T>,
+    ptr: *const T,
+    len: usize,
+    stride: usize,
+}
+
+impl<'a, T> StridedSlice<'a, T> {
+    /// Creates a strided slice from a pointer to its first item, the amount of
+    /// items, and the distance between two items in bytes.
+    ///
+    /// # Safety
+    ///
+    /// For every `i < len`, `ptr.byte_add(i * stride)` MUST point to a valid,
+    /// properly aligned `T` that is not mutated for `'a`.
+    pub unsafe fn from_raw_parts(ptr: *const T, len: usize, stride: usize) -> Self {
+        Self {
+            phantom_t: PhantomData,
+            ptr,
+            len,
+            stride,
+        }
+    }
+
+    /// Creates a strided slice over a normal slice. The stride will be the
+    /// size of `T`.
+    pub fn from_slice(slice: &'a [T]) -> Self {
+        // SAFETY: slices are contiguous, so every item is size_of::<T>() bytes
+        // after the previous one.
+        unsafe { Self::from_raw_parts(slice.as_ptr(), slice.len(), mem::size_of::<T>()) }
+    }
+
+    /// The amount of items in the slice.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The distance between two items, in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the pointer to the first item.
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Gets the item at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: index was bounds-checked above.
+        unsafe { Some(self.get_unchecked(index)) }
+    }
+
+    /// Gets the item at `index` without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` MUST be less than `self.len()`.
+    pub unsafe fn get_unchecked(&self, index: usize) -> &'a T {
+        &*self.ptr.byte_add(index * self.stride)
+    }
+
+    pub fn first(&self) -> Option<&'a T> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&'a T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+
+    /// Returns an iterator over the items.
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter {
+            phantom_t: PhantomData,
+            ptr: self.ptr,
+            len: self.len,
+            stride: self.stride,
+        }
+    }
+
+    /// Returns a strided slice over the items in `range` only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = slice_range(range, self.len);
+        // SAFETY: start..end is within the bounds of self.
+        unsafe {
+            Self::from_raw_parts(
+                self.ptr.wrapping_byte_add(start * self.stride),
+                end - start,
+                self.stride,
+            )
+        }
+    }
+
+    /// Whether the items are right next to each other, like in a normal
+    /// slice.
+    pub fn is_contiguous(&self) -> bool {
+        self.stride == mem::size_of::<T>()
+    }
+
+    /// Copies all items into `dst`. If the slice is contiguous, this is a
+    /// single memcpy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` does not have the same length as the slice.
+    pub fn copy_to_slice(&self, dst: &mut [T])
+    where
+        T: Copy,
+    {
+        assert_eq!(self.len, dst.len(), "lengths of the arrays do not match.");
+        if self.is_contiguous() {
+            // SAFETY: both are valid for len items, and dst is borrowed mutably, so they cannot
+            // overlap.
+            unsafe { ptr::copy_nonoverlapping(self.ptr, dst.as_mut_ptr(), self.len) };
+            return;
+        }
+        for (dst, src) in dst.iter_mut().zip(self.iter()) {
+            *dst = *src;
+        }
+    }
+
+    /// Clones all items into a new Vec.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Returns an iterator over chunks of `size` items. The last chunk is
+    /// shorter if the length is not divisible by `size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> Chunks<T, Self> {
+        Chunks::new(*self, size)
+    }
+
+    /// Returns an iterator over chunks of exactly `size` items. The items
+    /// left over at the end are in [`ChunksExact::remainder`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks_exact(&self, size: usize) -> ChunksExact<T, Self> {
+        ChunksExact::new(*self, size)
+    }
+
+    /// Divides the slice into two at `mid`. The first will contain all items
+    /// from `[0, mid)`, the second all items from `[mid, len)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len, "mid is too large for array.");
+        // SAFETY: both halves are within the bounds of self.
+        unsafe {
+            (
+                Self::from_raw_parts(self.ptr, mid, self.stride),
+                Self::from_raw_parts(
+                    self.ptr.wrapping_byte_add(mid * self.stride),
+                    self.len - mid,
+                    self.stride,
+                ),
+            )
+        }
+    }
+}
+
+impl<'a, T> Clone for StridedSlice<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for StridedSlice<'a, T> {}
+
+// SAFETY: a StridedSlice behaves like a &[T].
+unsafe impl<'a, T: Sync> Send for StridedSlice<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for StridedSlice<'a, T> {}
+
+impl<'a, T: fmt::Debug> fmt::Debug for StridedSlice<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T> Accessor<T, usize> for StridedSlice<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get<'b>(&'b self, index: usize) -> Option<&'b T> {
+        StridedSlice::get(self, index)
+    }
+}
+
+impl<'a, T> SplitAccessor<T> for StridedSlice<'a, T> {
+    fn into_split_at(self, mid: usize) -> (Self, Self) {
+        self.split_at(mid)
+    }
+}
+
+impl<'a, T> Index<usize> for StridedSlice<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.try_get(index).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<'a, T> IntoIterator for StridedSlice<'a, T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A mutable strided slice. Contains a pointer to the first item, the amount
+/// of items, and the distance between two items in bytes.
+pub struct StridedSliceMut<'a, T> {
+    phantom_t: PhantomData<&'a mut T>,
+    ptr: *mut T,
+    len: usize,
+    stride: usize,
+}
+
+impl<'a, T> StridedSliceMut<'a, T> {
+    /// Creates a mutable strided slice from a pointer to its first item, the
+    /// amount of items, and the distance between two items in bytes.
+    ///
+    /// # Safety
+    ///
+    /// For every `i < len`, `ptr.byte_add(i * stride)` MUST point to a valid,
+    /// properly aligned `T` that is not accessed through any other pointer for
+    /// `'a`. This also means `stride` MUST NOT be smaller than the size of `T`.
+    pub unsafe fn from_raw_parts(ptr: *mut T, len: usize, stride: usize) -> Self {
+        Self {
+            phantom_t: PhantomData,
+            ptr,
+            len,
+            stride,
+        }
+    }
+
+    /// Creates a mutable strided slice over a normal slice. The stride will be
+    /// the size of `T`.
+    pub fn from_slice(slice: &'a mut [T]) -> Self {
+        // SAFETY: slices are contiguous, so every item is size_of::<T>() bytes
+        // after the previous one.
+        unsafe { Self::from_raw_parts(slice.as_mut_ptr(), slice.len(), mem::size_of::<T>()) }
+    }
+
+    /// The amount of items in the slice.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The distance between two items, in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the pointer to the first item.
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Returns the mutable pointer to the first item.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.ptr
+    }
+
+    /// Returns an immutable view of this slice.
+    pub fn as_strided_slice(&self) -> StridedSlice<'_, T> {
+        // SAFETY: self is borrowed immutably for the lifetime of the view.
+        unsafe { StridedSlice::from_raw_parts(self.ptr, self.len, self.stride) }
+    }
+
+    /// Returns a mutable view of this slice with a shorter lifetime, so that
+    /// it can be passed on without giving up self.
+    pub fn reborrow(&mut self) -> StridedSliceMut<'_, T> {
+        // SAFETY: self is borrowed mutably for the lifetime of the view.
+        unsafe { StridedSliceMut::from_raw_parts(self.ptr, self.len, self.stride) }
+    }
+
+    /// Gets the item at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: index was bounds-checked above.
+        unsafe { Some(self.get_unchecked(index)) }
+    }
+
+    /// Gets the item at `index` mutably, or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: index was bounds-checked above.
+        unsafe { Some(self.get_unchecked_mut(index)) }
+    }
+
+    /// Gets the item at `index` without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` MUST be less than `self.len()`.
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        &*self.ptr.byte_add(index * self.stride)
+    }
+
+    /// Gets the item at `index` mutably without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` MUST be less than `self.len()`.
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        &mut *self.ptr.byte_add(index * self.stride)
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len.wrapping_sub(1))
+    }
+
+    /// Returns an iterator over the items.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.as_strided_slice().iter()
+    }
+
+    /// Returns an iterator that allows modifying each item.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            phantom_t: PhantomData,
+            ptr: self.ptr,
+            len: self.len,
+            stride: self.stride,
+        }
+    }
+
+    /// Returns an immutable strided slice over the items in `range` only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> StridedSlice<'_, T> {
+        self.as_strided_slice().slice(range)
+    }
+
+    /// Returns a mutable strided slice over the items in `range` only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn slice_mut(&mut self, range: impl RangeBounds<usize>) -> StridedSliceMut<'_, T> {
+        self.reborrow().into_slice(range)
+    }
+
+    /// Like [`Self::slice_mut`], but consumes self so the slice keeps its
+    /// lifetime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn into_slice(self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = slice_range(range, self.len);
+        // SAFETY: start..end is within the bounds of self.
+        unsafe {
+            Self::from_raw_parts(
+                self.ptr.wrapping_byte_add(start * self.stride),
+                end - start,
+                self.stride,
+            )
+        }
+    }
+
+    /// Whether the items are right next to each other, like in a normal
+    /// slice.
+    pub fn is_contiguous(&self) -> bool {
+        self.stride == mem::size_of::<T>()
+    }
+
+    /// Copies all items into `dst`. See [`StridedSlice::copy_to_slice`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` does not have the same length as the slice.
+    pub fn copy_to_slice(&self, dst: &mut [T])
+    where
+        T: Copy,
+    {
+        self.as_strided_slice().copy_to_slice(dst)
+    }
+
+    /// Clones all items into a new Vec.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.as_strided_slice().to_vec()
+    }
+
+    /// Copies all items from `src`. If the slice is contiguous, this is a
+    /// single memcpy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` does not have the same length as the slice.
+    pub fn copy_from_slice(&mut self, src: &[T])
+    where
+        T: Copy,
+    {
+        assert_eq!(self.len, src.len(), "lengths of the arrays do not match.");
+        if self.is_contiguous() {
+            // SAFETY: both are valid for len items, and self is borrowed mutably, so they cannot
+            // overlap.
+            unsafe { ptr::copy_nonoverlapping(src.as_ptr(), self.ptr, self.len) };
+            return;
+        }
+        for (dst, src) in self.iter_mut().zip(src) {
+            *dst = *src;
+        }
+    }
+
+    /// Clones all items from another accessor, which may be of a different
+    /// kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` does not have the same length as the slice.
+    pub fn clone_from_accessor<A: Accessor<T, usize> + ?Sized>(&mut self, src: &A)
+    where
+        T: Clone,
+    {
+        assert_eq!(self.len, src.len(), "lengths of the arrays do not match.");
+        for (i, dst) in self.iter_mut().enumerate() {
+            dst.clone_from(&src[i]);
+        }
+    }
+
+    /// Sets every item to a clone of `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for item in self.iter_mut() {
+            item.clone_from(&value);
+        }
+    }
+
+    /// Swaps the items at `a` and `b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        for index in [a, b] {
+            let len = self.len;
+            assert!(
+                index < len,
+                "{}",
+                SplitError::IndexOutOfBounds { index, len }
+            );
+        }
+        // SAFETY: both indices were bounds-checked above. ptr::swap allows them to be equal.
+        unsafe {
+            ptr::swap(
+                self.ptr.byte_add(a * self.stride),
+                self.ptr.byte_add(b * self.stride),
+            )
+        };
+    }
+
+    /// Reverses the order of the items.
+    pub fn reverse(&mut self) {
+        let mut iter = self.iter_mut();
+        while let (Some(a), Some(b)) = (iter.next(), iter.next_back()) {
+            mem::swap(a, b);
+        }
+    }
+
+    /// Rotates the items so that the item at `mid` becomes the first one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn rotate_left(&mut self, mid: usize) {
+        let (mut left, mut right) = self.split_at_mut(mid);
+        left.reverse();
+        right.reverse();
+        self.reverse();
+    }
+
+    /// Rotates the items so that the last `k` items come first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > len`.
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(k <= self.len, "k is too large for array.");
+        self.rotate_left(self.len - k);
+    }
+
+    /// Returns an iterator over immutable chunks of `size` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> Chunks<T, StridedSlice<'_, T>> {
+        self.as_strided_slice().chunks(size)
+    }
+
+    /// Returns an iterator over immutable chunks of exactly `size` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks_exact(&self, size: usize) -> ChunksExact<T, StridedSlice<'_, T>> {
+        self.as_strided_slice().chunks_exact(size)
+    }
+
+    /// Returns an iterator over mutable chunks of `size` items. The chunks do
+    /// not overlap, so they can be used at the same time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks_mut(&mut self, size: usize) -> Chunks<T, StridedSliceMut<'_, T>> {
+        Chunks::new(self.reborrow(), size)
+    }
+
+    /// Returns an iterator over mutable chunks of exactly `size` items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks_exact_mut(&mut self, size: usize) -> ChunksExact<T, StridedSliceMut<'_, T>> {
+        ChunksExact::new(self.reborrow(), size)
+    }
+
+    /// Divides the slice into two immutable halves at `mid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at(&self, mid: usize) -> (StridedSlice<'_, T>, StridedSlice<'_, T>) {
+        self.as_strided_slice().split_at(mid)
+    }
+
+    /// Divides the slice into two mutable halves at `mid`. The first will
+    /// contain all items from `[0, mid)`, the second all items from
+    /// `[mid, len)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at_mut(&mut self, mid: usize) -> (StridedSliceMut<'_, T>, StridedSliceMut<'_, T>) {
+        self.reborrow().into_split_at(mid)
+    }
+
+    /// Like [`Self::split_at_mut`], but consumes self so the halves keep its
+    /// lifetime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    pub fn into_split_at(self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len, "mid is too large for array.");
+        // SAFETY: both halves are within the bounds of self, and do not
+        // overlap.
+        unsafe {
+            (
+                Self::from_raw_parts(self.ptr, mid, self.stride),
+                Self::from_raw_parts(
+                    self.ptr.wrapping_byte_add(mid * self.stride),
+                    self.len - mid,
+                    self.stride,
+                ),
+            )
+        }
+    }
+}
+
+// SAFETY: a StridedSliceMut behaves like a &mut [T].
+unsafe impl<'a, T: Send> Send for StridedSliceMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for StridedSliceMut<'a, T> {}
+
+impl<'a, T: fmt::Debug> fmt::Debug for StridedSliceMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T> Accessor<T, usize> for StridedSliceMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get<'b>(&'b self, index: usize) -> Option<&'b T> {
+        StridedSliceMut::get(self, index)
+    }
+}
+
+impl<'a, T> AccessorMut<T, usize> for StridedSliceMut<'a, T> {
+    fn get_mut<'b>(&'b mut self, index: usize) -> Option<&'b mut T> {
+        StridedSliceMut::get_mut(self, index)
+    }
+}
+
+// SAFETY: the items are a stride apart, which is at least the size of T.
+unsafe impl<'a, T> DisjointAccessorMut<T> for StridedSliceMut<'a, T> {}
+
+impl<'a, T> SplitAccessor<T> for StridedSliceMut<'a, T> {
+    fn into_split_at(self, mid: usize) -> (Self, Self) {
+        StridedSliceMut::into_split_at(self, mid)
+    }
+}
+
+impl<'a, T> Index<usize> for StridedSliceMut<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.try_get(index).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<'a, T> IndexMut<usize> for StridedSliceMut<'a, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.try_get_mut(index)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<'a, T> IntoIterator for StridedSliceMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IterMut {
+            phantom_t: PhantomData,
+            ptr: self.ptr,
+            len: self.len,
+            stride: self.stride,
+        }
+    }
+}
+
+/// An iterator over a [`StridedSlice`].
+pub struct Iter<'a, T> {
+    phantom_t: PhantomData<&'a T>,
+    ptr: *const T,
+    len: usize,
+    stride: usize,
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Returns the items that have not been iterated yet as a strided slice.
+    pub fn as_strided_slice(&self) -> StridedSlice<'a, T> {
+        // SAFETY: the remaining items are a subset of the original slice.
+        unsafe { StridedSlice::from_raw_parts(self.ptr, self.len, self.stride) }
+    }
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: len is not 0, so ptr points to a valid item.
+        let item = unsafe { &*self.ptr };
+        self.ptr = self.ptr.wrapping_byte_add(self.stride);
+        self.len -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        self.ptr = self.ptr.wrapping_byte_add(n * self.stride);
+        self.len -= n;
+        self.next()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: len was not 0, so the last item is valid.
+        unsafe { Some(&*self.ptr.byte_add(self.len * self.stride)) }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+/// A mutable iterator over a [`StridedSliceMut`].
+pub struct IterMut<'a, T> {
+    phantom_t: PhantomData<&'a mut T>,
+    ptr: *mut T,
+    len: usize,
+    stride: usize,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    /// Returns the items that have not been iterated yet as a mutable strided
+    /// slice.
+    pub fn into_strided_slice(self) -> StridedSliceMut<'a, T> {
+        // SAFETY: the remaining items are a subset of the original slice and
+        // have not been handed out yet.
+        unsafe { StridedSliceMut::from_raw_parts(self.ptr, self.len, self.stride) }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: len is not 0, so ptr points to a valid item, and it will
+        // never be handed out again.
+        let item = unsafe { &mut *self.ptr };
+        self.ptr = self.ptr.wrapping_byte_add(self.stride);
+        self.len -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        self.ptr = self.ptr.wrapping_byte_add(n * self.stride);
+        self.len -= n;
+        self.next()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: len was not 0, so the last item is valid, and it will never
+        // be handed out again.
+        unsafe { Some(&mut *self.ptr.byte_add(self.len * self.stride)) }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+impl<'a, T> FusedIterator for IterMut<'a, T> {}
@@ -0,0 +1,242 @@
+//! Derive macros for the `Vector` and `RawVector` traits of vec_split. Use
+//! them through vec_split's `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Index, Member, Type};
+
+/// The fields of a struct that is used as a vector. All of them have the same
+/// type.
+struct VectorFields {
+    ty: Type,
+    members: Vec<Member>,
+}
+
+fn vector_fields(input: &DeriveInput) -> Result<VectorFields, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "vectors can only be derived for structs",
+        ));
+    };
+    let fields = match &data.fields {
+        Fields::Named(fields) => &fields.named,
+        Fields::Unnamed(fields) => &fields.unnamed,
+        Fields::Unit => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "vectors must have at least one field",
+            ))
+        }
+    };
+    let Some(first) = fields.first() else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "vectors must have at least one field",
+        ));
+    };
+    let ty = first.ty.clone();
+    let ty_string = ty.to_token_stream().to_string();
+    let mut members = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        if field.ty.to_token_stream().to_string() != ty_string {
+            return Err(Error::new_spanned(
+                &field.ty,
+                format!("all fields of a vector must have the same type, expected `{ty_string}`"),
+            ));
+        }
+        members.push(match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        });
+    }
+    Ok(VectorFields { ty, members })
+}
+
+/// Checks if the struct is `#[repr(C)]` or `#[repr(transparent)]`, and not
+/// packed, which could misalign its fields.
+fn check_repr(input: &DeriveInput) -> Result<(), Error> {
+    let mut fixed_layout = false;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repr") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") || meta.path.is_ident("transparent") {
+                fixed_layout = true;
+            }
+            if meta.path.is_ident("packed") {
+                return Err(meta.error("RawVector can not be derived for packed structs"));
+            }
+            // Skip the arguments of other representations, like align(N).
+            if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        })?;
+    }
+    if !fixed_layout {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "RawVector can only be derived for #[repr(C)] or #[repr(transparent)] structs, \
+             because the layout of other structs is not guaranteed",
+        ));
+    }
+    Ok(())
+}
+
+/// Derives `Vector<T, D>` for a struct whose fields all have the type `T`.
+/// `D` is the amount of fields, and they are indexed in the order they are
+/// declared in.
+#[proc_macro_derive(Vector)]
+pub fn derive_vector(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_vector(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_vector(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let VectorFields { ty, members } = vector_fields(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let d = members.len();
+    let indices = (0..d).collect::<Vec<_>>();
+    // SAFETY: every index is a distinct field, and the pointers are gotten with addr_of.
+    Ok(quote! {
+        unsafe impl #impl_generics ::vec_split::Vector<#ty, #d> for #name #ty_generics #where_clause {
+            fn get(&self, i: usize) -> ::core::option::Option<&#ty> {
+                match i {
+                    #(#indices => ::core::option::Option::Some(&self.#members),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            fn get_mut(&mut self, i: usize) -> ::core::option::Option<&mut #ty> {
+                match i {
+                    #(#indices => ::core::option::Option::Some(&mut self.#members),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            unsafe fn get_ptr(this: *const Self, i: usize) -> ::core::option::Option<*const #ty> {
+                match i {
+                    #(#indices => ::core::option::Option::Some(
+                        ::core::ptr::addr_of!((*this).#members)
+                    ),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            unsafe fn get_ptr_mut(this: *mut Self, i: usize) -> ::core::option::Option<*mut #ty> {
+                match i {
+                    #(#indices => ::core::option::Option::Some(
+                        ::core::ptr::addr_of_mut!((*this).#members)
+                    ),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    })
+}
+
+/// Derives `RawVector<T, D>` for a `#[repr(C)]` or `#[repr(transparent)]`
+/// struct whose fields all have the type `T`. `Vector<T, D>` must also be
+/// implemented, for example by deriving it. The struct must not have any
+/// padding, which is checked at compile time.
+#[proc_macro_derive(RawVector)]
+pub fn derive_raw_vector(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_raw_vector(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_raw_vector(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    check_repr(input)?;
+    let VectorFields { ty, members } = vector_fields(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let d = members.len();
+    let message = format!(
+        "{name} must not have any padding to be a RawVector: its size must be {d} times the size \
+         of its fields"
+    );
+    let size_check = quote! {
+        ::core::assert!(
+            ::core::mem::size_of::<#name #ty_generics>() == #d * ::core::mem::size_of::<#ty>(),
+            #message,
+        )
+    };
+    // Generic structs can only be checked once they are used.
+    let eager_check = input
+        .generics
+        .params
+        .is_empty()
+        .then(|| quote!(const _: () = #size_check;));
+    Ok(quote! {
+        #eager_check
+
+        // SAFETY: the struct is repr(C) or repr(transparent) and has no padding, so every field
+        // is a properly aligned T at its offset.
+        unsafe impl #impl_generics ::vec_split::RawVector<#ty, #d> for #name #ty_generics
+            #where_clause
+        {
+            const OFFSETS: [usize; #d] = {
+                #size_check;
+                [#(::core::mem::offset_of!(Self, #members),)*]
+            };
+        }
+    })
+}
+
+/// Derives `NamedVector<T, D>` for a struct with named fields that all have
+/// the type `T`. This creates a struct called `<Name>Split<A>`, with one field
+/// of type `A` for each field of the vector. `Vector<T, D>` must also be
+/// implemented, for example by deriving it.
+#[proc_macro_derive(NamedVector)]
+pub fn derive_named_vector(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_named_vector(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_named_vector(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let VectorFields { ty, members } = vector_fields(input)?;
+    let names = members
+        .iter()
+        .map(|member| match member {
+            Member::Named(ident) => Ok(ident),
+            Member::Unnamed(_) => Err(Error::new_spanned(
+                &input.ident,
+                "NamedVector can only be derived for structs with named fields",
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let name = &input.ident;
+    let vis = &input.vis;
+    let split_name = format_ident!("{}Split", name);
+    let split_doc = format!("The split dimensions of [`{name}`], one `A` for each of its fields.");
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let d = members.len();
+    Ok(quote! {
+        #[doc = #split_doc]
+        #[derive(Clone, Copy, Debug)]
+        #vis struct #split_name<A> {
+            #(pub #names: A,)*
+        }
+
+        impl #impl_generics ::vec_split::NamedVector<#ty, #d> for #name #ty_generics #where_clause {
+            type Split<A> = #split_name<A>;
+
+            fn name_split<A>(dims: [A; #d]) -> #split_name<A> {
+                let [#(#names,)*] = dims;
+                #split_name { #(#names,)* }
+            }
+        }
+    })
+}